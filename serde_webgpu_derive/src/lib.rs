@@ -0,0 +1,356 @@
+//! `#[derive(WgslLayout)]` for `serde_webgpu`, enabled by its `derive` feature.
+//!
+//! The derive works alongside `#[derive(Serialize)]`. It implements the static layout, adds an
+//! `OFFSET_*` constant per field and a `wgsl_decl()` function returning the WGSL struct.
+//!
+//! `#[wgsl(align = N)]` and `#[wgsl(size = N)]` become the WGSL `@align(N)` and `@size(N)`
+//! attributes, and the static layout includes them. A serde `Serialize` knows nothing of them, so
+//! alongside `#[derive(Serialize)]` it is asserted at compile time that they do not change the
+//! layout. `#[wgsl(serialize)]` on the struct implements `Serialize` as well, padding the members
+//! as the attributes say:
+//!
+//! ```
+//! # use serde_webgpu::layout::{verify, WgslLayout};
+//! # use serde_webgpu::mat::mat4x4;
+//! # use serde_webgpu::serialize_webgpu;
+//! # use serde_webgpu::vec::vec3;
+//! #[derive(WgslLayout, Default)]
+//! #[wgsl(serialize)]
+//! struct Camera {
+//!     view: mat4x4<f32>,
+//!     exposure: f32,
+//!     #[wgsl(align = 32)]
+//!     position: vec3<f32>,
+//!     #[wgsl(size = 16)]
+//!     near: f32,
+//! }
+//!
+//! const _: () = assert!(Camera::OFFSET_POSITION == 96 && Camera::SIZE == 128);
+//! assert_eq!(serialize_webgpu(&Camera::default()).unwrap().len(), 128);
+//! verify(&Camera::default()).unwrap();
+//! assert_eq!(
+//!     Camera::wgsl_decl(),
+//!     "struct Camera {\n    \
+//!      view: mat4x4<f32>,\n    \
+//!      exposure: f32,\n    \
+//!      @align(32) position: vec3<f32>,\n    \
+//!      @size(16) near: f32,\n\
+//!      }\n"
+//! );
+//! ```
+//!
+//! Without it, an attribute that would move a member fails to compile:
+//!
+//! ```compile_fail
+//! # use serde::Serialize;
+//! # use serde_webgpu::layout::WgslLayout;
+//! #[derive(Serialize, WgslLayout)]
+//! struct Misaligned {
+//!     a: f32,
+//!     #[wgsl(align = 16)]
+//!     b: f32,
+//! }
+//! ```
+//!
+//! `bool` members are declared as the `u32` they are widened to, so the declaration does not
+//! describe `BoolPolicy::Pack`.
+//!
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{parse_macro_input, Data, DeriveInput, Error, Fields, LitInt, Type};
+
+#[proc_macro_derive(WgslLayout, attributes(wgsl))]
+pub fn derive_wgsl_layout(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match expand(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+struct Field {
+    ident: syn::Ident,
+    name: String,
+    ty: Type,
+    offset: syn::Ident,
+    align: Option<usize>,
+    size: Option<usize>,
+}
+
+fn expand(input: &DeriveInput) -> Result<TokenStream, Error> {
+    let ident = &input.ident;
+    if !input.generics.params.is_empty() {
+        return Err(Error::new_spanned(
+            &input.generics,
+            "WgslLayout cannot be derived for generic structs",
+        ));
+    }
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(Error::new_spanned(
+                    ident,
+                    "WgslLayout needs named fields, as WGSL members have names",
+                ))
+            }
+        },
+        _ => {
+            return Err(Error::new_spanned(
+                ident,
+                "WgslLayout can only be derived for structs",
+            ))
+        }
+    };
+
+    let mut members = Vec::new();
+    for field in fields {
+        if is_skipped(field)? {
+            continue;
+        }
+        let field_ident = field.ident.clone().unwrap();
+        let name = field_ident.to_string();
+        let name = name.strip_prefix("r#").unwrap_or(&name).to_string();
+        let mut member = Field {
+            ident: field_ident,
+            offset: format_ident!("OFFSET_{}", name.to_uppercase()),
+            name,
+            ty: field.ty.clone(),
+            align: None,
+            size: None,
+        };
+        for attr in field.attrs.iter().filter(|i| i.path().is_ident("wgsl")) {
+            attr.parse_nested_meta(|meta| {
+                let value = || -> Result<usize, Error> {
+                    let lit: LitInt = meta.value()?.parse()?;
+                    lit.base10_parse()
+                };
+                if meta.path.is_ident("align") {
+                    let align = value()?;
+                    if !align.is_power_of_two() {
+                        return Err(meta.error("align must be a power of two"));
+                    }
+                    if align > 32 {
+                        return Err(meta.error("align larger than 32 is not supported"));
+                    }
+                    member.align = Some(align);
+                } else if meta.path.is_ident("size") {
+                    member.size = Some(value()?);
+                } else {
+                    return Err(meta.error("expected `align` or `size`"));
+                }
+                Ok(())
+            })?;
+        }
+        members.push(member);
+    }
+    if members.is_empty() {
+        return Err(Error::new_spanned(ident, "zero size type is not supported"));
+    }
+    let serialize = is_serialized(input)?;
+
+    let layout = quote!(::serde_webgpu::layout::WgslLayout);
+    let round_up = quote!(::serde_webgpu::layout::round_up);
+
+    // The layout of WGSL, with the attributes.
+    let mut offsets = Vec::new();
+    let mut aligns = Vec::new();
+    let mut assertions = Vec::new();
+    let mut end = quote!(0);
+    for Field {
+        name,
+        ty,
+        offset,
+        align,
+        size,
+        ..
+    } in &members
+    {
+        let member_align = match align {
+            Some(align) => {
+                let message = format!(
+                    "`align = {}` of `{}` is not a multiple of its alignment",
+                    align, name
+                );
+                assertions.push(quote!(assert!(#align % <#ty as #layout>::ALIGN == 0, #message);));
+                quote!(#align)
+            }
+            None => quote!(<#ty as #layout>::ALIGN),
+        };
+        let member_size = match size {
+            Some(size) => {
+                let message = format!("`size = {}` of `{}` is smaller than its type", size, name);
+                assertions.push(quote!(assert!(#size >= <#ty as #layout>::SIZE, #message);));
+                quote!(#size)
+            }
+            None => quote!(<#ty as #layout>::SIZE),
+        };
+        offsets.push(quote! {
+            pub const #offset: usize = #round_up(#member_align, #end);
+        });
+        aligns.push(quote!(if #member_align > align { align = #member_align; }));
+        end = quote!(#ident::#offset + #member_size);
+    }
+
+    // The layout of a serde `Serialize`, which ignores the attributes and must be the same.
+    if !serialize {
+        for Field {
+            name, ty, offset, ..
+        } in &members
+        {
+            let message = format!(
+                "the attributes of `{}` change its offset, which needs `#[wgsl(serialize)]`",
+                name
+            );
+            assertions.push(quote! {
+                let offset = #round_up(<#ty as #layout>::ALIGN, end);
+                assert!(offset == #ident::#offset, #message);
+                let end = offset + <#ty as #layout>::SIZE;
+                if <#ty as #layout>::ALIGN > align { align = <#ty as #layout>::ALIGN; }
+            });
+        }
+        let message = format!(
+            "the attributes of `{}` change its size or alignment, which needs `#[wgsl(serialize)]`",
+            ident
+        );
+        assertions.push(quote! {
+            assert!(
+                align == <#ident as #layout>::ALIGN
+                    && #round_up(align, end) == <#ident as #layout>::SIZE,
+                #message
+            );
+        });
+    }
+
+    let type_name = ident.to_string();
+    let fields = members.iter().map(|member| {
+        let (name, ty, offset) = (&member.name, &member.ty, &member.offset);
+        let align = member.align.map(|align| quote!(.with_align(#align)));
+        let size = member.size.map(|size| quote!(.with_size(#size)));
+        quote!(::serde_webgpu::layout::WgslField::new::<#ty>(#name, #ident::#offset)#align #size)
+    });
+    let decl = members.iter().map(|member| {
+        let prefix = format!("    {}{}: ", member.attributes(), member.name);
+        let ty = &member.ty;
+        quote! {
+            decl += #prefix;
+            decl += &<#ty as #layout>::wgsl_type();
+            decl += ",\n";
+        }
+    });
+    let header = format!("struct {} {{\n", ident);
+
+    let serialize = serialize.then(|| {
+        let count = members.len();
+        let members = members.iter().map(|member| {
+            let (field, name) = (&member.ident, &member.name);
+            let attributes = member.attributes();
+            match attributes.trim_end() {
+                "" => quote!(s.serialize_field(#name, &self.#field)?;),
+                attributes => quote! {
+                    s.serialize_field(
+                        #name,
+                        &::serde_webgpu::layout::WgslAttributes(#attributes, &self.#field),
+                    )?;
+                },
+            }
+        });
+        quote! {
+            impl ::serde_webgpu::serde::Serialize for #ident {
+                fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+                where
+                    S: ::serde_webgpu::serde::Serializer,
+                {
+                    use ::serde_webgpu::serde::ser::SerializeStruct;
+                    let mut s = serializer.serialize_struct(#type_name, #count)?;
+                    #(#members)*
+                    s.end()
+                }
+            }
+        }
+    });
+
+    Ok(quote! {
+        impl #ident {
+            #(#offsets)*
+
+            /// The WGSL declaration of this struct.
+            pub fn wgsl_decl() -> String {
+                let mut decl = String::from(#header);
+                #(#decl)*
+                decl += "}\n";
+                decl
+            }
+        }
+
+        impl #layout for #ident {
+            const SIZE: usize = #round_up(Self::ALIGN, #end);
+            const ALIGN: usize = {
+                let mut align = 0;
+                #(#aligns)*
+                align
+            };
+            const WGSL_TYPE_NAME: &'static str = #type_name;
+            const FIELDS: &'static [::serde_webgpu::layout::WgslField] = &[#(#fields),*];
+        }
+
+        #serialize
+
+        const _: () = {
+            let end = 0;
+            let mut align = 0;
+            #(#assertions)*
+        };
+    })
+}
+
+impl Field {
+    /// The WGSL attributes, each followed by a space.
+    fn attributes(&self) -> String {
+        let mut attributes = String::new();
+        if let Some(align) = self.align {
+            attributes += &format!("@align({}) ", align);
+        }
+        if let Some(size) = self.size {
+            attributes += &format!("@size({}) ", size);
+        }
+        attributes
+    }
+}
+
+/// Whether `#[wgsl(serialize)]` asks to implement `Serialize` with the attributes.
+fn is_serialized(input: &DeriveInput) -> Result<bool, Error> {
+    let mut serialize = false;
+    for attr in input.attrs.iter().filter(|i| i.path().is_ident("wgsl")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("serialize") {
+                serialize = true;
+                Ok(())
+            } else {
+                Err(meta.error("expected `serialize`"))
+            }
+        })?;
+    }
+    Ok(serialize)
+}
+
+/// Whether serde skips the field, so that it has no layout.
+fn is_skipped(field: &syn::Field) -> Result<bool, Error> {
+    let mut skipped = false;
+    for attr in field.attrs.iter().filter(|i| i.path().is_ident("serde")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("skip") || meta.path.is_ident("skip_serializing") {
+                skipped = true;
+            }
+            // Consume the value of other attributes such as `rename = "..."`.
+            if meta.input.peek(syn::Token![=]) {
+                meta.value()?.parse::<syn::Expr>()?;
+            } else if meta.input.peek(syn::token::Paren) {
+                meta.input.parse::<proc_macro2::Group>()?;
+            }
+            Ok(())
+        })?;
+    }
+    Ok(skipped)
+}
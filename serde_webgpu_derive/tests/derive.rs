@@ -0,0 +1,159 @@
+use serde::Serialize;
+
+use serde_webgpu::atomic::atomic;
+use serde_webgpu::layout::{verify, verify_with, WgslLayout};
+use serde_webgpu::mat::mat3x3;
+use serde_webgpu::padding::analyze_padding;
+use serde_webgpu::vec::{vec2, vec3, vec4};
+use serde_webgpu::{serialize_webgpu, AddressSpace, BoolPolicy, WebGPUConfig};
+
+#[derive(Serialize, WgslLayout, Default)]
+struct Light {
+    position: vec3<f32>,
+    range: f32,
+    color: vec4<f32>,
+}
+
+#[derive(Serialize, WgslLayout, Default)]
+struct Scene {
+    normal: mat3x3<f32>,
+    ambient: vec3<f32>,
+    lights: [Light; 2],
+    count: u32,
+    #[serde(skip)]
+    #[allow(dead_code)]
+    cpu_only: Vec<u8>,
+    #[wgsl(align = 8, size = 8)]
+    #[serde(rename = "jitter")]
+    r#jitter: vec2<f32>,
+}
+
+#[derive(Serialize, WgslLayout)]
+struct Counters {
+    visible: atomic<u32>,
+    culled: atomic<u32>,
+}
+
+#[derive(WgslLayout, Default)]
+#[wgsl(serialize)]
+struct Padded {
+    a: f32,
+    #[wgsl(align = 16, size = 20)]
+    b: vec2<f32>,
+    c: u32,
+}
+
+#[test]
+fn layout() {
+    assert_eq!(Light::SIZE, 32);
+    assert_eq!(Light::OFFSET_COLOR, 16);
+
+    assert_eq!(Scene::OFFSET_AMBIENT, 48);
+    assert_eq!(Scene::OFFSET_LIGHTS, 64);
+    assert_eq!(Scene::OFFSET_COUNT, 128);
+    assert_eq!(Scene::OFFSET_JITTER, 136);
+    assert_eq!((Scene::SIZE, Scene::ALIGN), (144, 16));
+    assert_eq!(Scene::FIELDS.len(), 5);
+    assert_eq!((Scene::FIELDS[2].wgsl_type)(), "array<Light, 2>");
+}
+
+#[test]
+fn runtime() {
+    verify(&Light::default()).unwrap();
+    verify(&Scene::default()).unwrap();
+
+    let config = WebGPUConfig {
+        address_space: AddressSpace::Storage,
+        ..Default::default()
+    };
+    let counters = Counters {
+        visible: atomic(0),
+        culled: atomic(0),
+    };
+    verify_with(&counters, &config).unwrap();
+}
+
+#[test]
+fn decl() {
+    assert_eq!(
+        Scene::wgsl_decl(),
+        "struct Scene {
+    normal: mat3x3<f32>,
+    ambient: vec3<f32>,
+    lights: array<Light, 2>,
+    count: u32,
+    @align(8) @size(8) jitter: vec2<f32>,
+}
+"
+    );
+    assert_eq!(
+        Counters::wgsl_decl(),
+        "struct Counters {\n    visible: atomic<u32>,\n    culled: atomic<u32>,\n}\n"
+    );
+}
+
+#[test]
+fn attributes() {
+    assert_eq!(Padded::OFFSET_B, 16);
+    assert_eq!(Padded::OFFSET_C, 36);
+    assert_eq!((Padded::SIZE, Padded::ALIGN), (48, 16));
+    verify(&Padded::default()).unwrap();
+
+    let padded = Padded {
+        a: 1.0,
+        b: vec2([2.0, 3.0]),
+        c: 4,
+    };
+    let buffer = serialize_webgpu(&padded).unwrap();
+    assert_eq!(buffer.len(), 48);
+    assert_eq!(buffer[16..20], 2.0f32.to_le_bytes());
+    assert_eq!(buffer[24..36], [0; 12]);
+    assert_eq!(buffer[36..40], 4u32.to_le_bytes());
+    assert_eq!(
+        Padded::wgsl_decl(),
+        "struct Padded {\n    a: f32,\n    @align(16) @size(20) b: vec2<f32>,\n    c: u32,\n}\n"
+    );
+}
+
+#[test]
+fn bool_pack() {
+    #[derive(Serialize, WgslLayout, Default)]
+    struct Flags {
+        a: bool,
+        b: bool,
+    }
+
+    assert_eq!(Flags::SIZE, 8);
+    verify(&Flags::default()).unwrap();
+    let pack = WebGPUConfig {
+        bool_policy: BoolPolicy::Pack,
+        ..Default::default()
+    };
+    assert!(verify_with(&Flags::default(), &pack).is_err());
+}
+
+#[test]
+fn padding() {
+    let report = analyze_padding::<Padded>();
+    // The 12 bytes that `@size(20)` adds to `b` are part of the member, not padding.
+    assert_eq!((report.padding(), report.savings()), (20, 16));
+    let suggested: Vec<_> = report
+        .suggested
+        .iter()
+        .map(|i| (i.name, i.offset, i.member_size()))
+        .collect();
+    assert_eq!(suggested, [("b", 0, 20), ("a", 20, 4), ("c", 24, 4)]);
+    let diff = report.diff();
+    let lines: Vec<_> = diff.lines().collect();
+    assert_eq!(
+        lines,
+        [
+            "  struct Padded {",
+            "-     a: f32,",
+            "      @align(16) @size(20) b: vec2<f32>,",
+            "+     a: f32,",
+            "      c: u32,",
+            "  }",
+        ]
+    );
+}
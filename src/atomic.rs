@@ -0,0 +1,59 @@
+#![allow(non_camel_case_types)]
+
+use std::sync::atomic::{AtomicI32, AtomicU32, Ordering};
+
+use serde::{Serialize, Serializer};
+
+use crate::layout::WgslLayout;
+
+/// WGSL `atomic<T>`, only valid in the storage address space.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct atomic<T>(pub T);
+
+impl<T> From<T> for atomic<T> {
+    fn from(value: T) -> Self {
+        Self(value)
+    }
+}
+
+impl From<&AtomicU32> for atomic<u32> {
+    fn from(value: &AtomicU32) -> Self {
+        Self(value.load(Ordering::Relaxed))
+    }
+}
+
+impl From<&AtomicI32> for atomic<i32> {
+    fn from(value: &AtomicI32) -> Self {
+        Self(value.load(Ordering::Relaxed))
+    }
+}
+
+impl WgslLayout for atomic<u32> {
+    const SIZE: usize = 4;
+    const ALIGN: usize = 4;
+    const WGSL_TYPE_NAME: &'static str = "atomic<u32>";
+}
+
+impl WgslLayout for atomic<i32> {
+    const SIZE: usize = 4;
+    const ALIGN: usize = 4;
+    const WGSL_TYPE_NAME: &'static str = "atomic<i32>";
+}
+
+impl Serialize for atomic<u32> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_newtype_struct("atomic@u32", &self.0)
+    }
+}
+
+impl Serialize for atomic<i32> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_newtype_struct("atomic@i32", &self.0)
+    }
+}
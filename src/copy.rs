@@ -0,0 +1,179 @@
+//! Staging buffer layouts for `copyBufferToTexture` and `copyTextureToBuffer`.
+//!
+//! Subresources are stored mip level by mip level, and all array layers of a mip level are
+//! contiguous, so each mip level can be copied with a single command. Every row is padded to
+//! [`COPY_BYTES_PER_ROW_ALIGNMENT`].
+//!
+//! ```
+//! # use serde_webgpu::copy::TextureCopyLayout;
+//! # use serde_webgpu::texture::TextureFormat;
+//! let layout = TextureCopyLayout::new(TextureFormat::Rgba8Unorm, 100, 100, 2, 1).unwrap();
+//!
+//! let mip = layout.subresource(1, 0).unwrap();
+//! assert_eq!(mip.offset, 512 * 100);
+//! assert_eq!(mip.bytes_per_row, 256);
+//! assert_eq!(mip.rows_per_image, 50);
+//!
+//! let tight = vec![7; layout.tight_size()];
+//! let padded = layout.pad(&tight).unwrap();
+//! assert_eq!(padded.len(), layout.size());
+//! assert_eq!(layout.unpad(&padded).unwrap(), tight);
+//! ```
+//!
+
+use crate::texture::TextureFormat;
+use crate::{ErrorKind, WebGPUSerializeError};
+
+pub const COPY_BYTES_PER_ROW_ALIGNMENT: usize = 256;
+
+/// The copy layout of a single mip level of a single array layer.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Subresource {
+    pub mip_level: u32,
+    pub array_layer: u32,
+    pub width: u32,
+    pub height: u32,
+    /// Offset in the padded staging buffer.
+    pub offset: usize,
+    pub bytes_per_row: usize,
+    /// Rows of texel blocks, not of texels.
+    pub rows_per_image: usize,
+    /// Offset in the tightly packed data.
+    pub tight_offset: usize,
+    pub tight_bytes_per_row: usize,
+}
+
+#[derive(Clone, Debug)]
+pub struct TextureCopyLayout {
+    format: TextureFormat,
+    subresources: Vec<Subresource>,
+    size: usize,
+    tight_size: usize,
+}
+
+impl TextureCopyLayout {
+    pub fn new(
+        format: TextureFormat,
+        width: u32,
+        height: u32,
+        mip_level_count: u32,
+        array_layer_count: u32,
+    ) -> Result<Self, WebGPUSerializeError> {
+        if width == 0 || height == 0 || mip_level_count == 0 || array_layer_count == 0 {
+            return Err(WebGPUSerializeError::new(
+                ErrorKind::Size,
+                "texture copy layout is empty",
+            ));
+        }
+        let max_mip_level_count = 32 - width.max(height).leading_zeros();
+        if mip_level_count > max_mip_level_count {
+            return Err(WebGPUSerializeError::new(
+                ErrorKind::Size,
+                format!(
+                    "mip level count {} exceeds {} for a {}x{} texture",
+                    mip_level_count, max_mip_level_count, width, height
+                ),
+            ));
+        }
+
+        let (block_width, block_height) = format.block_dimensions();
+        let block_size = format.texel_size();
+        let mut subresources = Vec::new();
+        let mut offset = 0;
+        let mut tight_offset = 0;
+        for mip_level in 0..mip_level_count {
+            let width = (width >> mip_level).max(1);
+            let height = (height >> mip_level).max(1);
+            let tight_bytes_per_row = width.div_ceil(block_width) as usize * block_size;
+            let bytes_per_row = tight_bytes_per_row.next_multiple_of(COPY_BYTES_PER_ROW_ALIGNMENT);
+            let rows_per_image = height.div_ceil(block_height) as usize;
+            for array_layer in 0..array_layer_count {
+                subresources.push(Subresource {
+                    mip_level,
+                    array_layer,
+                    width,
+                    height,
+                    offset,
+                    bytes_per_row,
+                    rows_per_image,
+                    tight_offset,
+                    tight_bytes_per_row,
+                });
+                offset += bytes_per_row * rows_per_image;
+                tight_offset += tight_bytes_per_row * rows_per_image;
+            }
+        }
+
+        Ok(Self {
+            format,
+            subresources,
+            size: offset,
+            tight_size: tight_offset,
+        })
+    }
+
+    pub fn format(&self) -> TextureFormat {
+        self.format
+    }
+
+    pub fn subresources(&self) -> &[Subresource] {
+        &self.subresources
+    }
+
+    pub fn subresource(&self, mip_level: u32, array_layer: u32) -> Option<&Subresource> {
+        self.subresources
+            .iter()
+            .find(|i| i.mip_level == mip_level && i.array_layer == array_layer)
+    }
+
+    /// The size of the padded staging buffer.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// The size of the tightly packed data.
+    pub fn tight_size(&self) -> usize {
+        self.tight_size
+    }
+
+    /// Insert the row padding into tightly packed data.
+    pub fn pad(&self, tight: &[u8]) -> Result<Vec<u8>, WebGPUSerializeError> {
+        check_size("tightly packed", tight.len(), self.tight_size)?;
+        let mut buffer = vec![0; self.size];
+        for i in &self.subresources {
+            for row in 0..i.rows_per_image {
+                let src = i.tight_offset + row * i.tight_bytes_per_row;
+                let dst = i.offset + row * i.bytes_per_row;
+                buffer[dst..dst + i.tight_bytes_per_row]
+                    .copy_from_slice(&tight[src..src + i.tight_bytes_per_row]);
+            }
+        }
+        Ok(buffer)
+    }
+
+    /// Strip the row padding from a buffer read back from the GPU.
+    pub fn unpad(&self, padded: &[u8]) -> Result<Vec<u8>, WebGPUSerializeError> {
+        check_size("padded", padded.len(), self.size)?;
+        let mut buffer = Vec::with_capacity(self.tight_size);
+        for i in &self.subresources {
+            for row in 0..i.rows_per_image {
+                let src = i.offset + row * i.bytes_per_row;
+                buffer.extend_from_slice(&padded[src..src + i.tight_bytes_per_row]);
+            }
+        }
+        Ok(buffer)
+    }
+}
+
+fn check_size(name: &str, size: usize, expected: usize) -> Result<(), WebGPUSerializeError> {
+    if size != expected {
+        return Err(WebGPUSerializeError::new(
+            ErrorKind::Size,
+            format!(
+                "{} data size {} does not match the layout size {}",
+                name, size, expected
+            ),
+        ));
+    }
+    Ok(())
+}
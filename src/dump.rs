@@ -0,0 +1,171 @@
+//! An annotated hex dump of a serialized value, listing every member with its path, offset, size
+//! and type next to its bytes, and marking the padding in between.
+//!
+//! ```
+//! # use serde::Serialize;
+//! # use serde_webgpu::dump::dump_webgpu;
+//! # use serde_webgpu::vec::vec3;
+//! #[derive(Serialize)]
+//! struct Light {
+//!     intensity: f32,
+//!     position: vec3<f32>,
+//! }
+//!
+//! let light = Light {
+//!     intensity: 1.0,
+//!     position: vec3([0.0, 2.0, 0.0]),
+//! };
+//! let dump = dump_webgpu(&light).unwrap();
+//! assert_eq!(dump.field("position").unwrap().offset, 16);
+//! assert_eq!(dump.padding(), 16);
+//! println!("{}", dump);
+//! //      0..4       intensity: f32
+//! //                00 00 80 3f
+//! //      4..16      padding
+//! //                00 00 00 00 00 00 00 00 00 00 00 00
+//! //     16..28      position: vec3<f32>
+//! //                00 00 00 00 00 00 00 40 00 00 00 00
+//! //     28..32      padding
+//! //                00 00 00 00
+//! ```
+//!
+
+use std::fmt::{Display, Formatter};
+use std::ops::Range;
+
+use serde::Serialize;
+
+use crate::{serialize_webgpu_block, WebGPUBlock, WebGPUConfig, WebGPUItem, WebGPUSerializeError};
+
+/// A scalar, vector, matrix or atomic member of the value.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DumpField {
+    /// The path of the member, such as `lights[0].position`.
+    pub path: String,
+    /// The WGSL type, such as `vec3<f32>` or `bool as u32`. Scalars without a WGSL type, such
+    /// as `u8`, `i16` or `bytes`, keep their Rust type, since the shader unpacks them from the
+    /// `u32` they share.
+    pub wgsl_type: String,
+    pub offset: usize,
+    pub size: usize,
+}
+
+impl DumpField {
+    pub fn range(&self) -> Range<usize> {
+        self.offset..self.offset + self.size
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct LayoutDump {
+    bytes: Vec<u8>,
+    fields: Vec<DumpField>,
+}
+
+impl LayoutDump {
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    pub fn fields(&self) -> &[DumpField] {
+        &self.fields
+    }
+
+    pub fn field(&self, path: &str) -> Option<&DumpField> {
+        self.fields.iter().find(|i| i.path == path)
+    }
+
+    /// The ranges of bytes not covered by any member.
+    pub fn padding_ranges(&self) -> Vec<Range<usize>> {
+        let mut ranges = Vec::new();
+        let mut offset = 0;
+        for field in &self.fields {
+            if field.offset > offset {
+                ranges.push(offset..field.offset);
+            }
+            offset = offset.max(field.offset + field.size);
+        }
+        if self.bytes.len() > offset {
+            ranges.push(offset..self.bytes.len());
+        }
+        ranges
+    }
+
+    /// The number of padding bytes.
+    pub fn padding(&self) -> usize {
+        self.padding_ranges().iter().map(|i| i.len()).sum()
+    }
+}
+
+impl Display for LayoutDump {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut padding = self.padding_ranges().into_iter().peekable();
+        for field in &self.fields {
+            while let Some(range) = padding.next_if(|i| i.start < field.offset) {
+                write_region(f, range, "padding", &self.bytes)?;
+            }
+            let label = match field.path.as_str() {
+                "" => field.wgsl_type.clone(),
+                path => format!("{}: {}", path, field.wgsl_type),
+            };
+            write_region(f, field.range(), &label, &self.bytes)?;
+        }
+        for range in padding {
+            write_region(f, range, "padding", &self.bytes)?;
+        }
+        Ok(())
+    }
+}
+
+fn write_region(
+    f: &mut Formatter<'_>,
+    range: Range<usize>,
+    label: &str,
+    bytes: &[u8],
+) -> std::fmt::Result {
+    writeln!(f, "{:>6}..{:<6}  {}", range.start, range.end, label)?;
+    for line in bytes[range].chunks(16) {
+        write!(f, "{:14}", "")?;
+        for byte in line {
+            write!(f, " {:02x}", byte)?;
+        }
+        writeln!(f)?;
+    }
+    Ok(())
+}
+
+pub fn dump_webgpu<T: Serialize>(value: &T) -> Result<LayoutDump, WebGPUSerializeError> {
+    dump_webgpu_with(value, &WebGPUConfig::default())
+}
+
+pub fn dump_webgpu_with<T: Serialize>(
+    value: &T,
+    config: &WebGPUConfig,
+) -> Result<LayoutDump, WebGPUSerializeError> {
+    let block = WebGPUBlock {
+        fields: Some(Vec::new()),
+        ..Default::default()
+    };
+    let (block, _) = serialize_webgpu_block(value, config, block)?;
+    let bytes = block.compute_layout()?;
+    let item_ends = block.item_ends();
+    let data = |i: usize| match block.items[i] {
+        WebGPUItem::Data(length) => Some((item_ends[i] - length, item_ends[i])),
+        _ => None,
+    };
+    let mut fields = Vec::new();
+    for field in block.fields.unwrap_or_default() {
+        let (start, end) = field.items;
+        let first = (start..end).find_map(data);
+        let last = (start..end).rev().find_map(data);
+        if let (Some((offset, _)), Some((_, end))) = (first, last) {
+            fields.push(DumpField {
+                path: field.path,
+                wgsl_type: field.wgsl_type,
+                offset,
+                size: end - offset,
+            });
+        }
+    }
+    Ok(LayoutDump { bytes, fields })
+}
@@ -0,0 +1,124 @@
+//! Fieldless enums are serialized as an `u32` of the variant index.
+//!
+//! Use [`wgsl_enum!`](crate::wgsl_enum) to serialize the explicit discriminants instead, and to
+//! generate the matching WGSL constants.
+//!
+//! ```
+//! # use serde_webgpu::enums::WgslEnum;
+//! # use serde_webgpu::{serialize_webgpu, wgsl_enum};
+//! wgsl_enum! {
+//!     #[derive(Copy, Clone, Debug)]
+//!     pub enum LightKind {
+//!         Point = 1,
+//!         Spot = 2,
+//!         Directional = 4,
+//!     }
+//! }
+//!
+//! assert_eq!(serialize_webgpu(&LightKind::Directional).unwrap(), [4, 0, 0, 0]);
+//! assert_eq!(
+//!     LightKind::wgsl_consts(),
+//!     "const LIGHT_KIND_POINT: u32 = 1u;\n\
+//!      const LIGHT_KIND_SPOT: u32 = 2u;\n\
+//!      const LIGHT_KIND_DIRECTIONAL: u32 = 4u;\n",
+//! );
+//! ```
+//!
+
+/// A fieldless enum with known variant values.
+pub trait WgslEnum {
+    const NAME: &'static str;
+    const VARIANTS: &'static [(&'static str, u32)];
+
+    /// A WGSL `const` declaration for each variant.
+    fn wgsl_consts() -> String {
+        let prefix = screaming_snake_case(Self::NAME);
+        let mut wgsl = String::new();
+        for (variant, value) in Self::VARIANTS {
+            wgsl += &format!(
+                "const {}_{}: u32 = {}u;\n",
+                prefix,
+                screaming_snake_case(variant),
+                value
+            );
+        }
+        wgsl
+    }
+}
+
+pub(crate) fn screaming_snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut result = String::new();
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            let prev = chars[i - 1];
+            let next_lower = chars.get(i + 1).is_some_and(|c| c.is_lowercase());
+            if prev.is_lowercase() || (prev.is_alphanumeric() && next_lower) {
+                result.push('_');
+            }
+        }
+        result.extend(c.to_uppercase());
+    }
+    result
+}
+
+/// Define a fieldless enum that serializes its discriminant and implements [`WgslEnum`].
+///
+/// Every discriminant must fit in an `u32`, which is checked at compile time.
+///
+/// ```compile_fail
+/// # use serde_webgpu::wgsl_enum;
+/// wgsl_enum! {
+///     #[repr(i32)]
+///     enum Sign {
+///         Negative = -1,
+///         Positive = 1,
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! wgsl_enum {
+    (
+        $(#[$meta:meta])*
+        $vis:vis enum $name:ident {
+            $($(#[$variant_meta:meta])* $variant:ident $(= $value:expr)?),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        $vis enum $name {
+            $($(#[$variant_meta])* $variant $(= $value)?),*
+        }
+
+        const _: () = {
+            $(assert!(
+                $name::$variant as i128 >= 0 && $name::$variant as i128 <= u32::MAX as i128,
+                concat!(
+                    "discriminant of `",
+                    stringify!($name),
+                    "::",
+                    stringify!($variant),
+                    "` does not fit in an u32"
+                ),
+            );)*
+        };
+
+        impl $crate::enums::WgslEnum for $name {
+            const NAME: &'static str = stringify!($name);
+            const VARIANTS: &'static [(&'static str, u32)] =
+                &[$((stringify!($variant), $name::$variant as u32)),*];
+        }
+
+        impl $crate::serde::Serialize for $name {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: $crate::serde::Serializer,
+            {
+                // A newtype rather than a unit variant, whose index must be the position.
+                let value = match self {
+                    $($name::$variant => $name::$variant as u32),*
+                };
+                serializer.serialize_newtype_struct("wgsl_enum", &value)
+            }
+        }
+    };
+}
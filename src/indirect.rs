@@ -0,0 +1,142 @@
+//! Arguments of `drawIndirect`, `drawIndexedIndirect` and `dispatchWorkgroupsIndirect`.
+//!
+//! ```
+//! # use serde_webgpu::indirect::{decode_indirect_args, serialize_indirect_args, DrawIndexedIndirectArgs};
+//! let draws = [
+//!     DrawIndexedIndirectArgs { index_count: 36, instance_count: 1, ..Default::default() },
+//!     DrawIndexedIndirectArgs { index_count: 6, instance_count: 8, first_index: 36, ..Default::default() },
+//! ];
+//!
+//! let buffer = serialize_indirect_args(&draws).unwrap();
+//! assert_eq!(buffer.len(), 2 * 20);
+//! assert_eq!(decode_indirect_args::<DrawIndexedIndirectArgs>(&buffer).unwrap(), draws);
+//! ```
+//!
+
+use serde::Serialize;
+
+use crate::{serialize_webgpu, ErrorKind, WebGPUSerializeError};
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// Indirect arguments with a fixed size in bytes.
+///
+/// The trait is sealed, since [`Self::SIZE`] must match the serialized layout.
+pub trait IndirectArgs: Serialize + Sized + sealed::Sealed {
+    const SIZE: usize;
+
+    /// Decode the arguments from exactly [`Self::SIZE`] bytes.
+    fn decode(bytes: &[u8]) -> Self;
+}
+
+fn read_u32(bytes: &[u8], index: usize) -> u32 {
+    u32::from_le_bytes(bytes[index * 4..index * 4 + 4].try_into().unwrap())
+}
+
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Serialize)]
+pub struct DrawIndirectArgs {
+    pub vertex_count: u32,
+    pub instance_count: u32,
+    pub first_vertex: u32,
+    pub first_instance: u32,
+}
+
+impl sealed::Sealed for DrawIndirectArgs {}
+
+impl IndirectArgs for DrawIndirectArgs {
+    const SIZE: usize = 16;
+
+    fn decode(bytes: &[u8]) -> Self {
+        Self {
+            vertex_count: read_u32(bytes, 0),
+            instance_count: read_u32(bytes, 1),
+            first_vertex: read_u32(bytes, 2),
+            first_instance: read_u32(bytes, 3),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Serialize)]
+pub struct DrawIndexedIndirectArgs {
+    pub index_count: u32,
+    pub instance_count: u32,
+    pub first_index: u32,
+    pub base_vertex: i32,
+    pub first_instance: u32,
+}
+
+impl sealed::Sealed for DrawIndexedIndirectArgs {}
+
+impl IndirectArgs for DrawIndexedIndirectArgs {
+    const SIZE: usize = 20;
+
+    fn decode(bytes: &[u8]) -> Self {
+        Self {
+            index_count: read_u32(bytes, 0),
+            instance_count: read_u32(bytes, 1),
+            first_index: read_u32(bytes, 2),
+            base_vertex: read_u32(bytes, 3) as i32,
+            first_instance: read_u32(bytes, 4),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Serialize)]
+pub struct DispatchIndirectArgs {
+    pub x: u32,
+    pub y: u32,
+    pub z: u32,
+}
+
+impl sealed::Sealed for DispatchIndirectArgs {}
+
+impl IndirectArgs for DispatchIndirectArgs {
+    const SIZE: usize = 12;
+
+    fn decode(bytes: &[u8]) -> Self {
+        Self {
+            x: read_u32(bytes, 0),
+            y: read_u32(bytes, 1),
+            z: read_u32(bytes, 2),
+        }
+    }
+}
+
+/// Serialize tightly packed arguments for multi-draw-indirect.
+pub fn serialize_indirect_args<T: IndirectArgs>(
+    args: &[T],
+) -> Result<Vec<u8>, WebGPUSerializeError> {
+    let mut buffer = Vec::with_capacity(args.len() * T::SIZE);
+    for i in args {
+        let bytes = serialize_webgpu(i)?;
+        if bytes.len() != T::SIZE {
+            return Err(WebGPUSerializeError::new(
+                ErrorKind::Size,
+                format!(
+                    "arguments serialize to {} bytes instead of {}",
+                    bytes.len(),
+                    T::SIZE
+                ),
+            ));
+        }
+        buffer.extend_from_slice(&bytes);
+    }
+    Ok(buffer)
+}
+
+/// Decode tightly packed arguments read back from the GPU.
+pub fn decode_indirect_args<T: IndirectArgs>(bytes: &[u8]) -> Result<Vec<T>, WebGPUSerializeError> {
+    if bytes.len() % T::SIZE != 0 {
+        return Err(WebGPUSerializeError::new(
+            ErrorKind::Size,
+            format!(
+                "buffer size {} is not a multiple of {}",
+                bytes.len(),
+                T::SIZE
+            ),
+        ));
+    }
+    Ok(bytes.chunks_exact(T::SIZE).map(T::decode).collect())
+}
@@ -0,0 +1,196 @@
+//! Layouts known at compile time, to size buffers and write `const` assertions without a value.
+//!
+//! The layout is the one [`serialize_webgpu`](crate::serialize_webgpu) produces with 64-bit
+//! scalars enabled and `bool` widened to `u32`. [`verify`] cross-checks the two for a value.
+//!
+//! ```
+//! # use serde::Serialize;
+//! # use serde_webgpu::layout::{round_up, verify, WgslField, WgslLayout};
+//! # use serde_webgpu::vec::vec3;
+//! #[derive(Serialize)]
+//! struct Light {
+//!     position: vec3<f32>,
+//!     intensity: f32,
+//! }
+//!
+//! impl WgslLayout for Light {
+//!     const SIZE: usize = 16;
+//!     const ALIGN: usize = 16;
+//!     const WGSL_TYPE_NAME: &'static str = "Light";
+//!     const FIELDS: &'static [WgslField] = &[
+//!         WgslField::new::<vec3<f32>>("position", 0),
+//!         WgslField::new::<f32>("intensity", 12),
+//!     ];
+//! }
+//!
+//! const _: () = assert!(<[Light; 8]>::SIZE == 128);
+//! assert_eq!(<[Light; 8]>::wgsl_type(), "array<Light, 8>");
+//! assert_eq!(round_up(16, 20), 32);
+//!
+//! verify(&Light { position: vec3([0.0; 3]), intensity: 1.0 }).unwrap();
+//! ```
+//!
+
+use serde::Serialize;
+
+use crate::{serialize_webgpu_layout, BoolPolicy, ErrorKind, WebGPUConfig, WebGPUSerializeError};
+
+/// Derive the layout of a struct, with the `derive` feature.
+#[cfg(feature = "derive")]
+pub use serde_webgpu_derive::WgslLayout;
+
+/// A type whose WGSL layout is known at compile time.
+pub trait WgslLayout {
+    const SIZE: usize;
+    const ALIGN: usize;
+    /// The WGSL type name, without the parameters of types such as `array`.
+    const WGSL_TYPE_NAME: &'static str;
+    /// The members of a struct, empty for other types.
+    const FIELDS: &'static [WgslField] = &[];
+    /// The stride of the type as an array element.
+    const STRIDE: usize = round_up(Self::ALIGN, Self::SIZE);
+
+    /// The full WGSL type, such as `array<vec3<f32>, 4>`.
+    fn wgsl_type() -> String {
+        Self::WGSL_TYPE_NAME.to_string()
+    }
+}
+
+/// A member serialized with its WGSL attributes, such as `@align(16) @size(32)`, used by
+/// `#[wgsl(serialize)]`.
+#[doc(hidden)]
+pub struct WgslAttributes<'a, T: ?Sized>(pub &'static str, pub &'a T);
+
+impl<T: ?Sized + Serialize> Serialize for WgslAttributes<'_, T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_newtype_struct(self.0, self.1)
+    }
+}
+
+/// A struct member in [`WgslLayout::FIELDS`].
+#[derive(Copy, Clone, Debug)]
+pub struct WgslField {
+    pub name: &'static str,
+    pub offset: usize,
+    /// The size of the type, without `@size`.
+    pub size: usize,
+    /// The alignment of the type, without `@align`.
+    pub align: usize,
+    pub align_attribute: Option<usize>,
+    pub size_attribute: Option<usize>,
+    pub wgsl_type: fn() -> String,
+    /// The members of a struct member, empty for other types.
+    pub fields: &'static [WgslField],
+}
+
+impl WgslField {
+    pub const fn new<T: WgslLayout>(name: &'static str, offset: usize) -> Self {
+        Self {
+            name,
+            offset,
+            size: T::SIZE,
+            align: T::ALIGN,
+            align_attribute: None,
+            size_attribute: None,
+            wgsl_type: T::wgsl_type,
+            fields: T::FIELDS,
+        }
+    }
+
+    /// The member with `@align(align)`.
+    pub const fn with_align(mut self, align: usize) -> Self {
+        self.align_attribute = Some(align);
+        self
+    }
+
+    /// The member with `@size(size)`.
+    pub const fn with_size(mut self, size: usize) -> Self {
+        self.size_attribute = Some(size);
+        self
+    }
+
+    /// The alignment of the member, including `@align`.
+    pub const fn member_align(&self) -> usize {
+        match self.align_attribute {
+            Some(align) => align,
+            None => self.align,
+        }
+    }
+
+    /// The size of the member, including `@size`.
+    pub const fn member_size(&self) -> usize {
+        match self.size_attribute {
+            Some(size) => size,
+            None => self.size,
+        }
+    }
+
+    /// The WGSL attributes of the member, such as `@align(16) @size(32) `.
+    pub fn attributes(&self) -> String {
+        let mut attributes = String::new();
+        if let Some(align) = self.align_attribute {
+            attributes += &format!("@align({}) ", align);
+        }
+        if let Some(size) = self.size_attribute {
+            attributes += &format!("@size({}) ", size);
+        }
+        attributes
+    }
+}
+
+/// Round `n` up to a multiple of `align`.
+pub const fn round_up(align: usize, n: usize) -> usize {
+    n.div_ceil(align) * align
+}
+
+impl<T: WgslLayout, const N: usize> WgslLayout for [T; N] {
+    const SIZE: usize = N * T::STRIDE;
+    const ALIGN: usize = T::ALIGN;
+    const WGSL_TYPE_NAME: &'static str = "array";
+
+    fn wgsl_type() -> String {
+        format!("array<{}, {}>", T::wgsl_type(), N)
+    }
+}
+
+/// Check that `value` serializes to the static layout of its type.
+pub fn verify<T: WgslLayout + Serialize>(value: &T) -> Result<(), WebGPUSerializeError> {
+    let mut config = WebGPUConfig::default();
+    config.extensions.int64 = true;
+    config.extensions.f64 = true;
+    verify_with(value, &config)
+}
+
+/// Like [`verify`], with a configuration other than the one of the static layout.
+///
+/// [`BoolPolicy::Pack`] is rejected, since the static layout and `wgsl_decl()` widen each `bool`
+/// to its own `u32`.
+pub fn verify_with<T: WgslLayout + Serialize>(
+    value: &T,
+    config: &WebGPUConfig,
+) -> Result<(), WebGPUSerializeError> {
+    if config.bool_policy == BoolPolicy::Pack {
+        return Err(WebGPUSerializeError::new(
+            ErrorKind::Unsupported,
+            "the static layout widens `bool` to `u32`, so it does not describe `BoolPolicy::Pack`",
+        ));
+    }
+    let (size, align) = serialize_webgpu_layout(value, config)?;
+    if size != T::SIZE || align != T::ALIGN {
+        return Err(WebGPUSerializeError::new(
+            ErrorKind::Size,
+            format!(
+                "{} serializes with size {} and align {}, but its static layout has size {} and align {}",
+                T::wgsl_type(),
+                size,
+                align,
+                T::SIZE,
+                T::ALIGN
+            ),
+        ));
+    }
+    Ok(())
+}
@@ -14,12 +14,12 @@
 //!
 //! let uniform = Uniform {
 //!     a: f16::from_f32(123.456),
-//!     b: [
+//!     b: mat4x4([
 //!         vec4([1.0, 2.0, 3.0, 4.0]),
 //!         vec4([4.0, 5.0, 7.0, 8.0]),
 //!         vec4([1.0, 2.0, 3.0, 4.0]),
 //!         vec4([5.0, 6.0, 7.0, 8.0]),
-//!     ],
+//!     ]),
 //! };
 //!
 //! serialize_webgpu_buffer(&uniform).unwrap();
@@ -29,12 +29,41 @@
 use std::cmp::max;
 use std::fmt::{Debug, Display, Formatter};
 
-use serde::ser::{Impossible, SerializeSeq, SerializeStruct, SerializeTuple, SerializeTupleStruct};
+use serde::ser::{
+    Impossible, SerializeSeq, SerializeStruct, SerializeStructVariant, SerializeTuple,
+    SerializeTupleStruct, SerializeTupleVariant,
+};
 use serde::{Serialize, Serializer};
 
+use crate::scalar::scalar_layout;
+use crate::vec::vector_align;
+
+pub mod atomic;
+pub mod copy;
+pub mod dump;
+pub mod enums;
+pub mod indirect;
+pub mod layout;
 pub mod mat;
+pub mod mesh;
+pub mod option;
+pub mod pack;
+pub mod packed;
+pub mod padding;
+pub mod scalar;
+pub mod string;
+pub mod tagged;
+pub mod texture;
 pub mod vec;
 
+#[doc(hidden)]
+pub use serde;
+
+// The README examples use the derive.
+#[cfg(all(doctest, feature = "derive"))]
+#[doc = include_str!("../README.md")]
+struct ReadmeDoctests;
+
 #[allow(non_camel_case_types)]
 pub type f16 = half::f16;
 
@@ -47,6 +76,7 @@ enum Align {
     Align4,
     Align8,
     Align16,
+    Align32,
 }
 
 impl Align {
@@ -64,41 +94,241 @@ impl Align {
             Align::Align4 => 4,
             Align::Align8 => 8,
             Align::Align16 => 16,
+            Align::Align32 => 32,
+        }
+    }
+    fn from_value(value: usize) -> Self {
+        match value {
+            0 => Align::Align0,
+            1 => Align::Align1,
+            2 => Align::Align2,
+            4 => Align::Align4,
+            8 => Align::Align8,
+            16 => Align::Align16,
+            32 => Align::Align32,
+            _ => unreachable!(),
+        }
+    }
+}
+
+/// Whether the name tag is a matrix, such as `mat4x3@f32`.
+fn is_matrix(name: &str) -> bool {
+    name.starts_with("mat") && name.contains('@')
+}
+
+/// The WGSL type of a name tag, such as `vec3<f32>` for `vec3@f32`.
+struct TagTypeName<'a>(&'a str);
+
+impl Display for TagTypeName<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.0.split_once('@') {
+            Some((outer, inner)) => write!(f, "{}<{}>", outer, inner),
+            None => f.write_str(self.0),
         }
     }
 }
 
+/// The alignment and size of `@align(N) @size(N)`, either of which may be missing.
+fn member_attributes(name: &str) -> Option<(Align, Option<usize>)> {
+    let mut align = Align::Align0;
+    let mut size = None;
+    for attribute in name.split_whitespace() {
+        if let Some(n) = attribute.strip_prefix("@align(") {
+            let n: usize = n.strip_suffix(')')?.parse().ok()?;
+            if !n.is_power_of_two() || n > 32 {
+                return None;
+            }
+            align = Align::from_value(n);
+        } else if let Some(n) = attribute.strip_prefix("@size(") {
+            size = Some(n.strip_suffix(')')?.parse().ok()?);
+        } else {
+            return None;
+        }
+    }
+    Some((align, size))
+}
+
+/// The alignment and size of a vector from its name tag, such as `vec3@f32` or
+/// `packed_vec3@f32`.
+fn vector_layout(name: &str, len: usize, config: &WebGPUConfig) -> Option<(Align, usize)> {
+    let (packed, name) = match name.strip_prefix("packed_") {
+        Some(name) => (true, name),
+        None => (false, name),
+    };
+    let prefix = match len {
+        2 => "vec2@",
+        3 => "vec3@",
+        4 => "vec4@",
+        _ => return None,
+    };
+    let scalar = name.strip_prefix(prefix)?;
+    let (size, align) = match (scalar, scalar_layout(scalar)?) {
+        // The components are packed into the bits of a single `u32`.
+        ("bool", _) if config.bool_policy == BoolPolicy::Pack => return Some((Align::Align4, 4)),
+        // Narrowed to 32 bits without their extension.
+        ("i64" | "u64", _) if !config.extensions.int64 => (4, 4),
+        ("f64", _) if !config.extensions.f64 => (4, 4),
+        (_, layout) => layout,
+    };
+    let align = match config.layout {
+        _ if packed => align,
+        LayoutMode::Scalar | LayoutMode::HlslCbuffer | LayoutMode::HlslStructured => align,
+        _ => vector_align(len, align),
+    };
+    Some((Align::from_value(align), size * len))
+}
+
 #[derive(Copy, Clone, Debug)]
 enum WebGPUItem {
     Align(Align),
     Data(usize),
+    /// Move to the next 16-byte register if the next `size` bytes would straddle one.
+    Register(usize),
+    /// Pad with zeros until `size` bytes follow the item at `from`, or report an error of `kind`
+    /// if more do.
+    Fill {
+        from: usize,
+        size: usize,
+        kind: ErrorKind,
+    },
+}
+
+#[derive(Clone, Debug)]
+enum UnionContext {
+    /// Measure the payload of a sample variant.
+    Measure,
+    /// Write a variant with the measured payload layout.
+    Write(UnionLayout),
+    /// Capture the tag of an internally tagged enum.
+    Tag,
+    /// Capture the variant names numbering the tags of internally tagged enums.
+    Names(Vec<String>),
+}
+
+#[derive(Clone, Debug, Default)]
+struct UnionLayout {
+    size: usize,
+    align: Align,
+    /// Variant names in declaration order, from [`tagged::WgslUnion::VARIANTS`].
+    variants: Vec<String>,
+    /// Variant indices of externally tagged samples.
+    indices: Vec<u32>,
+    /// Variant names of internally tagged samples.
+    names: Vec<String>,
+}
+
+#[derive(Clone, Debug)]
+enum VariantTag {
+    Index(u32, &'static str),
+    Name(String),
+}
+
+/// A leaf member recorded for a layout dump.
+#[derive(Clone, Debug)]
+struct WebGPUField {
+    path: String,
+    wgsl_type: String,
+    /// The range of items holding its data.
+    items: (usize, usize),
 }
 
 #[derive(Clone, Debug, Default)]
 struct WebGPUBlock {
     buffer: Vec<u8>,
     items: Vec<WebGPUItem>,
+    /// Buffer offset and used bits of the bitmask that consecutive bools are packed into.
+    bool_pack: Option<(usize, usize)>,
+    /// Set for the enum value serialized next as a tagged union.
+    union: Option<UnionContext>,
+    /// The variant met while measuring a union sample or capturing a tag.
+    variant: Option<VariantTag>,
+    /// The next `u16` holds the bits of an `f16`.
+    f16: bool,
+    /// The leaf members, when recording a layout dump.
+    fields: Option<Vec<WebGPUField>>,
+    /// The path of the member being serialized, when recording a layout dump.
+    path: Vec<String>,
+    /// The nesting of vectors and matrices, whose components are not recorded.
+    leaf_depth: usize,
 }
 
 impl WebGPUBlock {
     fn append(&mut self, i: &[u8]) {
+        self.bool_pack = None;
         self.buffer.extend_from_slice(i);
         self.items.push(WebGPUItem::Data(i.len()));
     }
 
     fn align(&mut self, align: Align) -> usize {
+        self.bool_pack = None;
         let index = self.items.len();
         self.items.push(WebGPUItem::Align(align));
         index
     }
 
-    fn get_align(&self, index: usize) -> Align {
-        match self.items[index] {
-            WebGPUItem::Align(a) => a,
-            _ => unreachable!(),
+    fn append_bool_pack(&mut self, v: bool) {
+        match self.bool_pack {
+            Some((offset, bit)) if bit < 32 => {
+                self.buffer[offset + bit / 8] |= (v as u8) << (bit % 8);
+                self.bool_pack = Some((offset, bit + 1));
+                self.label(format_args!("bool in bit {} of u32", bit));
+            }
+            _ => {
+                self.align(Align::Align4);
+                let offset = self.buffer.len();
+                self.append(&u32::to_le_bytes(v as u32));
+                self.bool_pack = Some((offset, 1));
+                self.label("bool in bit 0 of u32");
+            }
         }
     }
 
+    /// Record the last item as a leaf member of a layout dump.
+    fn label(&mut self, wgsl_type: impl Display) {
+        self.label_from(self.items.len() - 1, wgsl_type);
+    }
+
+    /// Record the items from `start` as a leaf member of a layout dump.
+    fn label_from(&mut self, start: usize, wgsl_type: impl Display) {
+        if self.leaf_depth > 0 {
+            return;
+        }
+        if let Some(fields) = &mut self.fields {
+            let mut path = String::new();
+            for segment in &self.path {
+                if !path.is_empty() && !segment.starts_with('[') {
+                    path.push('.');
+                }
+                path += segment;
+            }
+            fields.push(WebGPUField {
+                path,
+                wgsl_type: wgsl_type.to_string(),
+                items: (start, self.items.len()),
+            });
+        }
+    }
+
+    fn enter(&mut self, segment: impl FnOnce() -> String) {
+        if self.fields.is_some() {
+            self.path.push(segment());
+        }
+    }
+
+    fn leave(&mut self) {
+        self.path.pop();
+    }
+
+    fn register(&mut self, size: usize) {
+        self.bool_pack = None;
+        self.items.push(WebGPUItem::Register(size));
+    }
+
+    fn fill(&mut self, from: usize, size: usize, kind: ErrorKind) {
+        self.bool_pack = None;
+        self.items.push(WebGPUItem::Fill { from, size, kind });
+    }
+
     fn align_append(&mut self, index: usize, align: Align) {
         match &mut self.items[index] {
             WebGPUItem::Align(a) => a.append(align),
@@ -106,9 +336,36 @@ impl WebGPUBlock {
         }
     }
 
-    fn compute_layout(&self) -> Vec<u8> {
+    /// The size of the layout so far, like the length of [`Self::compute_layout`].
+    fn size(&self) -> usize {
+        self.item_ends().last().copied().unwrap_or(0)
+    }
+
+    /// The offset after each item in [`Self::compute_layout`].
+    fn item_ends(&self) -> Vec<usize> {
+        let mut size: usize = 0;
+        let mut item_ends = Vec::with_capacity(self.items.len());
+        for &i in &self.items {
+            size = match i {
+                WebGPUItem::Align(align) => size.next_multiple_of(align.value().max(1)),
+                WebGPUItem::Data(length) => size + length,
+                WebGPUItem::Register(length) if size % 16 + length > 16 => {
+                    size.next_multiple_of(16)
+                }
+                WebGPUItem::Register(_) => size,
+                WebGPUItem::Fill {
+                    from, size: fill, ..
+                } => size.max(item_ends[from] + fill),
+            };
+            item_ends.push(size);
+        }
+        item_ends
+    }
+
+    fn compute_layout(&self) -> Result<Vec<u8>, WebGPUSerializeError> {
         let mut buffer = Vec::new();
         let mut offset = 0;
+        let mut item_ends = Vec::with_capacity(self.items.len());
         for &i in &self.items {
             match i {
                 WebGPUItem::Align(Align::Align0) => {}
@@ -116,32 +373,155 @@ impl WebGPUBlock {
                     let a = align.value() - 1;
                     let p = buffer.len();
                     let s = (p + a) & !a;
-                    for _ in 0..(s - p) {
-                        buffer.push(0);
-                    }
+                    buffer.resize(s, 0);
                 }
                 WebGPUItem::Data(length) => {
                     let next_offset = offset + length;
                     buffer.extend_from_slice(&self.buffer[offset..next_offset]);
                     offset = next_offset;
                 }
+                WebGPUItem::Register(size) => {
+                    if buffer.len() % 16 + size > 16 {
+                        buffer.resize(buffer.len().next_multiple_of(16), 0);
+                    }
+                }
+                WebGPUItem::Fill { from, size, kind } => {
+                    let end = item_ends[from] + size;
+                    if buffer.len() > end {
+                        let msg = match kind {
+                            ErrorKind::Union => format!(
+                                "union payload exceeds the {} bytes of the sample variants",
+                                size
+                            ),
+                            _ => format!("member exceeds its `@size({})`", size),
+                        };
+                        let error = WebGPUSerializeError::new(kind, msg);
+                        return Err(error.at_offset(end));
+                    }
+                    buffer.resize(end, 0);
+                }
             }
+            item_ends.push(buffer.len());
         }
         assert_eq!(offset, self.buffer.len());
-        buffer
+        Ok(buffer)
     }
 }
 
+/// The kind of a [`WebGPUSerializeError`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// A type without a WGSL layout, such as a map, a `str` or an enum outside a union.
+    Unsupported,
+    /// A `bool` rejected by [`BoolPolicy::Reject`].
+    NotHostShareable,
+    /// A type not allowed in the address space, such as an atomic in a uniform buffer.
+    AddressSpace,
+    /// A value out of the range of the type it is narrowed to.
+    Overflow,
+    /// A struct, tuple or array with no data, which has no WGSL equivalent.
+    ZeroSize,
+    /// A union value or sample which does not fit the union.
+    Union,
+    /// A size or count which does not match the layout.
+    Size,
+    /// A value exceeding a fixed capacity, such as a too long [`string::WgslString`].
+    Capacity,
+    /// An error raised by a `Serialize` implementation.
+    Custom,
+}
+
 #[derive(Debug)]
 pub struct WebGPUSerializeError {
+    kind: ErrorKind,
     msg: String,
+    path: String,
+    type_name: Option<&'static str>,
+    offset: Option<usize>,
+}
+
+impl WebGPUSerializeError {
+    pub(crate) fn new(kind: ErrorKind, msg: impl Into<String>) -> Self {
+        Self {
+            kind,
+            msg: msg.into(),
+            path: String::new(),
+            type_name: None,
+            offset: None,
+        }
+    }
+
+    pub(crate) fn with_type(mut self, type_name: &'static str) -> Self {
+        self.type_name.get_or_insert(type_name);
+        self
+    }
+
+    fn in_field(self, key: &str) -> Self {
+        self.in_segment(key.to_string())
+    }
+
+    fn in_index(self, index: usize) -> Self {
+        self.in_segment(format!("[{}]", index))
+    }
+
+    fn in_segment(mut self, mut segment: String) -> Self {
+        if !self.path.is_empty() && !self.path.starts_with('[') {
+            segment.push('.');
+        }
+        self.path.insert_str(0, &segment);
+        self
+    }
+
+    fn at_offset(mut self, offset: usize) -> Self {
+        self.offset.get_or_insert(offset);
+        self
+    }
+
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+
+    /// The message without the location.
+    pub fn message(&self) -> &str {
+        &self.msg
+    }
+
+    /// The path of the member from the serialized value, such as `lights[2].kind`.
+    pub fn path(&self) -> Option<&str> {
+        Some(self.path.as_str()).filter(|i| !i.is_empty())
+    }
+
+    /// The Rust type the error was raised for, when serde names it.
+    pub fn type_name(&self) -> Option<&'static str> {
+        self.type_name
+    }
+
+    /// The offset in the layout reached by the serialization.
+    pub fn offset(&self) -> Option<usize> {
+        self.offset
+    }
 }
 
 impl std::error::Error for WebGPUSerializeError {}
 
 impl Display for WebGPUSerializeError {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        f.write_str(&self.msg)
+        f.write_str(&self.msg)?;
+        let mut location = Vec::new();
+        if let Some(path) = self.path() {
+            location.push(format!("at `{}`", path));
+        }
+        if let Some(type_name) = self.type_name {
+            location.push(format!("type `{}`", type_name));
+        }
+        if let Some(offset) = self.offset {
+            location.push(format!("offset {}", offset));
+        }
+        if !location.is_empty() {
+            write!(f, " ({})", location.join(", "))?;
+        }
+        Ok(())
     }
 }
 
@@ -150,14 +530,149 @@ impl serde::ser::Error for WebGPUSerializeError {
     where
         T: Display,
     {
-        Self {
-            msg: format!("{}", msg),
+        Self::new(ErrorKind::Custom, msg.to_string())
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum AddressSpace {
+    #[default]
+    Uniform,
+    Storage,
+}
+
+/// How `bool`, which is not host-shareable in WGSL, is laid out.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum BoolPolicy {
+    /// Reject `bool` as the WGSL specification does.
+    Reject,
+    /// Widen to an `u32` of 0 or 1. This is the default, where `bool` used to be written as a
+    /// single byte.
+    #[default]
+    Widen,
+    /// Pack consecutive `bool`s into the bits of an `u32`, starting at the lowest bit.
+    ///
+    /// Only `bool`s in the same struct or array share an `u32`: a nested struct or array starts
+    /// a new one, and so does the first `bool` after it.
+    Pack,
+}
+
+/// Native shader extensions beyond WebGPU, which enable 64-bit scalars with 8-byte alignment.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct ShaderExtensions {
+    /// `SHADER_INT64`, for `i64` and `u64`.
+    pub int64: bool,
+    /// `SHADER_F64`, for `f64`.
+    pub f64: bool,
+}
+
+/// How `f64` is converted when the `SHADER_F64` extension is disabled.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum F64Policy {
+    #[default]
+    Reject,
+    /// Round to the nearest `f32`, overflowing to infinity.
+    Lossy,
+    /// Round to the nearest `f32`, rejecting finite values out of the `f32` range. Precision is
+    /// lost silently, so `0.1` is accepted.
+    Checked,
+}
+
+/// Conversions of Rust scalars that WGSL does not have.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct NumericPolicy {
+    /// Narrow `i64`, `u64` and `usize` to `i32` and `u32` when the `SHADER_INT64` extension is
+    /// disabled, rejecting values that overflow.
+    pub narrow_int64: bool,
+    pub f64: F64Policy,
+    /// Widen `i8`, `u8`, `i16` and `u16` to 32 bits.
+    pub widen_small_ints: bool,
+}
+
+/// The rules the memory layout follows.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum LayoutMode {
+    /// The WGSL layout of the storage address space.
+    #[default]
+    Wgsl,
+    /// GLSL `std140` uniform blocks, where structs, arrays and matrices are aligned to 16 bytes,
+    /// and so is every array element and matrix column.
+    Std140,
+    /// GLSL `std430` buffer blocks, which match the WGSL layout.
+    Std430,
+    /// `VK_EXT_scalar_block_layout`, where vectors and matrices are aligned like their scalar.
+    Scalar,
+    /// HLSL constant buffers, where vectors are aligned like their scalar but do not straddle
+    /// 16-byte registers. Structs, array elements and matrix columns start on a register, and
+    /// structs pad to one.
+    HlslCbuffer,
+    /// HLSL `StructuredBuffer`, where vectors and matrices are aligned like their scalar.
+    HlslStructured,
+    /// Metal Shading Language, where `float3` takes 16 bytes. Use [`packed::PackedVector`] for
+    /// `packed_float3`.
+    Msl,
+}
+
+impl LayoutMode {
+    /// The minimum alignment of structs, arrays and matrices.
+    fn aggregate_align(self) -> Align {
+        match self {
+            LayoutMode::Std140 | LayoutMode::HlslCbuffer => Align::Align16,
+            _ => Align::Align0,
+        }
+    }
+
+    /// The minimum alignment of array elements and matrix columns.
+    fn element_align(self) -> Align {
+        match self {
+            LayoutMode::Std140 | LayoutMode::HlslCbuffer => Align::Align16,
+            _ => Align::Align0,
         }
     }
+
+    /// Whether arrays and matrices are padded to their alignment, rather than letting the next
+    /// member use the rest of the last element.
+    fn pads_arrays(self) -> bool {
+        self != LayoutMode::HlslCbuffer
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct WebGPUConfig {
+    pub layout: LayoutMode,
+    pub address_space: AddressSpace,
+    pub bool_policy: BoolPolicy,
+    pub extensions: ShaderExtensions,
+    pub numeric: NumericPolicy,
+}
+
+fn unsupported(type_name: &'static str) -> WebGPUSerializeError {
+    WebGPUSerializeError::new(
+        ErrorKind::Unsupported,
+        format!("{} is not supported", type_name),
+    )
+    .with_type(type_name)
+}
+
+fn unsupported_option() -> WebGPUSerializeError {
+    WebGPUSerializeError::new(
+        ErrorKind::Unsupported,
+        "option is not supported, see `serde_webgpu::option`",
+    )
+    .with_type("Option")
+}
+
+fn overflow(type_name: &'static str, v: impl Display, target: &str) -> WebGPUSerializeError {
+    WebGPUSerializeError::new(
+        ErrorKind::Overflow,
+        format!("{} value {} overflows {}", type_name, v, target),
+    )
+    .with_type(type_name)
 }
 
 struct WebGPUSerializer<'s> {
     write: &'s mut WebGPUBlock,
+    config: &'s WebGPUConfig,
 }
 
 impl<'s> Serializer for WebGPUSerializer<'s> {
@@ -167,99 +682,198 @@ impl<'s> Serializer for WebGPUSerializer<'s> {
     type SerializeSeq = WebGPUSerializeStruct<'s>;
     type SerializeTuple = WebGPUSerializeStruct<'s>;
     type SerializeTupleStruct = WebGPUSerializeStruct<'s>;
-    type SerializeTupleVariant = Impossible<Self::Ok, Self::Error>;
+    type SerializeTupleVariant = WebGPUSerializeStruct<'s>;
     type SerializeMap = Impossible<Self::Ok, Self::Error>;
     type SerializeStruct = WebGPUSerializeStruct<'s>;
-    type SerializeStructVariant = Impossible<Self::Ok, Self::Error>;
+    type SerializeStructVariant = WebGPUSerializeStruct<'s>;
 
     fn serialize_bool(self, v: bool) -> Result<Self::Ok, Self::Error> {
-        self.write.append(&[v as u8]);
-        Ok(Align::Align1)
+        match self.config.bool_policy {
+            BoolPolicy::Reject => Err(WebGPUSerializeError::new(
+                ErrorKind::NotHostShareable,
+                "bool is not host-shareable",
+            )
+            .with_type("bool")),
+            BoolPolicy::Widen => {
+                self.write.align(Align::Align4);
+                self.write.append(&u32::to_le_bytes(v as u32));
+                self.write.label("bool as u32");
+                Ok(Align::Align4)
+            }
+            BoolPolicy::Pack => {
+                self.write.append_bool_pack(v);
+                Ok(Align::Align4)
+            }
+        }
     }
 
     fn serialize_i8(self, v: i8) -> Result<Self::Ok, Self::Error> {
+        if self.config.numeric.widen_small_ints {
+            return self.serialize_i32(v as i32);
+        }
         self.write.append(&[v as u8]);
+        self.write.label("i8");
         Ok(Align::Align1)
     }
 
     fn serialize_i16(self, v: i16) -> Result<Self::Ok, Self::Error> {
+        if self.config.numeric.widen_small_ints {
+            return self.serialize_i32(v as i32);
+        }
         self.write.align(Align::Align2);
         self.write.append(&i16::to_le_bytes(v));
+        self.write.label("i16");
         Ok(Align::Align4)
     }
 
     fn serialize_i32(self, v: i32) -> Result<Self::Ok, Self::Error> {
         self.write.align(Align::Align4);
         self.write.append(&i32::to_le_bytes(v));
+        self.write.label("i32");
         Ok(Align::Align4)
     }
 
-    fn serialize_i64(self, _v: i64) -> Result<Self::Ok, Self::Error> {
-        Err(serde::ser::Error::custom("i64 is not supported"))
+    fn serialize_i64(self, v: i64) -> Result<Self::Ok, Self::Error> {
+        if !self.config.extensions.int64 {
+            if !self.config.numeric.narrow_int64 {
+                return Err(unsupported("i64"));
+            }
+            return match i32::try_from(v) {
+                Ok(v) => self.serialize_i32(v),
+                Err(_) => Err(overflow("i64", v, "i32")),
+            };
+        }
+        self.write.align(Align::Align8);
+        self.write.append(&i64::to_le_bytes(v));
+        self.write.label("i64");
+        Ok(Align::Align8)
     }
 
     fn serialize_u8(self, v: u8) -> Result<Self::Ok, Self::Error> {
+        if self.config.numeric.widen_small_ints {
+            return self.serialize_u32(v as u32);
+        }
         self.write.append(&[v]);
+        self.write.label("u8");
         Ok(Align::Align1)
     }
 
     fn serialize_u16(self, v: u16) -> Result<Self::Ok, Self::Error> {
+        if std::mem::take(&mut self.write.f16) {
+            self.write.align(Align::Align2);
+            self.write.append(&u16::to_le_bytes(v));
+            self.write.label("f16");
+            return Ok(Align::Align2);
+        }
+        if self.config.numeric.widen_small_ints {
+            return self.serialize_u32(v as u32);
+        }
         self.write.align(Align::Align4);
         self.write.append(&u16::to_le_bytes(v));
+        self.write.label("u16");
         Ok(Align::Align4)
     }
 
     fn serialize_u32(self, v: u32) -> Result<Self::Ok, Self::Error> {
         self.write.align(Align::Align4);
         self.write.append(&u32::to_le_bytes(v));
+        self.write.label("u32");
         Ok(Align::Align4)
     }
 
-    fn serialize_u64(self, _v: u64) -> Result<Self::Ok, Self::Error> {
-        Err(serde::ser::Error::custom("u64 is not supported"))
+    fn serialize_u64(self, v: u64) -> Result<Self::Ok, Self::Error> {
+        if !self.config.extensions.int64 {
+            if !self.config.numeric.narrow_int64 {
+                return Err(unsupported("u64"));
+            }
+            return match u32::try_from(v) {
+                Ok(v) => self.serialize_u32(v),
+                Err(_) => Err(overflow("u64", v, "u32")),
+            };
+        }
+        self.write.align(Align::Align8);
+        self.write.append(&u64::to_le_bytes(v));
+        self.write.label("u64");
+        Ok(Align::Align8)
     }
 
     fn serialize_f32(self, v: f32) -> Result<Self::Ok, Self::Error> {
         self.write.align(Align::Align4);
         self.write.append(&f32::to_le_bytes(v));
+        self.write.label("f32");
         Ok(Align::Align4)
     }
 
-    fn serialize_f64(self, _v: f64) -> Result<Self::Ok, Self::Error> {
-        Err(serde::ser::Error::custom("f64 is not supported"))
+    fn serialize_f64(self, v: f64) -> Result<Self::Ok, Self::Error> {
+        if !self.config.extensions.f64 {
+            return match self.config.numeric.f64 {
+                F64Policy::Reject => Err(unsupported("f64")),
+                F64Policy::Lossy => self.serialize_f32(v as f32),
+                F64Policy::Checked if v.is_finite() && (v as f32).is_infinite() => {
+                    Err(overflow("f64", format_args!("{:e}", v), "f32"))
+                }
+                F64Policy::Checked => self.serialize_f32(v as f32),
+            };
+        }
+        self.write.align(Align::Align8);
+        self.write.append(&f64::to_le_bytes(v));
+        self.write.label("f64");
+        Ok(Align::Align8)
     }
 
     fn serialize_char(self, v: char) -> Result<Self::Ok, Self::Error> {
         self.write.align(Align::Align4);
         self.write.append(&u32::to_le_bytes(v as u32));
+        self.write.label("u32");
         Ok(Align::Align4)
     }
 
     fn serialize_str(self, v: &str) -> Result<Self::Ok, Self::Error> {
-        self.write.align(Align::Align1);
-        self.write.append(v.as_bytes());
-        Ok(Align::Align1)
+        match &mut self.write.union {
+            Some(UnionContext::Tag) => {
+                self.write.union = None;
+                self.write.variant = Some(VariantTag::Name(v.to_string()));
+                return Ok(Align::Align0);
+            }
+            Some(UnionContext::Names(names)) => {
+                names.push(v.to_string());
+                return Ok(Align::Align0);
+            }
+            _ => {}
+        }
+        Err(WebGPUSerializeError::new(
+            ErrorKind::Unsupported,
+            format!(
+                "str of {} bytes has no fixed size, use `WgslString`",
+                v.len()
+            ),
+        )
+        .with_type("str"))
     }
 
     fn serialize_bytes(self, v: &[u8]) -> Result<Self::Ok, Self::Error> {
         self.write.align(Align::Align1);
         self.write.append(v);
+        self.write.label("bytes");
         Ok(Align::Align1)
     }
 
     fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
-        Err(serde::ser::Error::custom("enum is not supported"))
+        // A `None` carries no layout, so a fixed layout needs the wrappers of `option`.
+        Err(unsupported_option())
     }
 
-    fn serialize_some<T: ?Sized>(self, _value: &T) -> Result<Self::Ok, Self::Error>
+    fn serialize_some<T>(self, _value: &T) -> Result<Self::Ok, Self::Error>
     where
-        T: Serialize,
+        T: ?Sized + Serialize,
     {
-        Err(serde::ser::Error::custom("enum is not supported"))
+        Err(unsupported_option())
     }
 
     fn serialize_unit(self) -> Result<Self::Ok, Self::Error> {
-        self.serialize_tuple(0)?.end()
+        let mut s = self.serialize_tuple(0)?;
+        s.name = Some("()");
+        s.end()
     }
 
     fn serialize_unit_struct(self, name: &'static str) -> Result<Self::Ok, Self::Error> {
@@ -268,24 +882,103 @@ impl<'s> Serializer for WebGPUSerializer<'s> {
 
     fn serialize_unit_variant(
         self,
-        _name: &'static str,
-        _variant_index: u32,
-        _variant: &'static str,
+        name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
     ) -> Result<Self::Ok, Self::Error> {
-        Err(serde::ser::Error::custom("enum is not supported"))
+        if self.write.union.is_some() {
+            return self.serialize_variant(name, variant_index, variant)?.end();
+        }
+        self.serialize_u32(variant_index)
     }
 
-    fn serialize_newtype_struct<T: ?Sized>(
+    fn serialize_newtype_struct<T>(
         self,
         name: &'static str,
         value: &T,
     ) -> Result<Self::Ok, Self::Error>
     where
-        T: Serialize,
+        T: ?Sized + Serialize,
     {
         match name {
+            // A `tagged::Union`, which passes its samples to this serializer only.
+            "union" => {
+                tagged::WEBGPU_UNION.with(|i| i.set(true));
+                let align = value.serialize(WebGPUSerializer {
+                    write: &mut *self.write,
+                    config: self.config,
+                });
+                tagged::WEBGPU_UNION.with(|i| i.set(false));
+                return align;
+            }
+            // The discriminant of a `wgsl_enum!`.
+            "wgsl_enum" => {
+                return value.serialize(WebGPUSerializer {
+                    write: self.write,
+                    config: self.config,
+                });
+            }
+            // The `None` of an `OptionZeroed`, a default sample written as zeros.
+            "option@zeroed" => {
+                let start = self.write.buffer.len();
+                let align = value.serialize(WebGPUSerializer {
+                    write: &mut *self.write,
+                    config: self.config,
+                })?;
+                self.write.buffer[start..].fill(0);
+                return Ok(align);
+            }
+            // A member with `@align(N)` or `@size(N)`, from `#[derive(WgslLayout)]`.
+            name if name.starts_with('@') => {
+                let (align, size) = match member_attributes(name) {
+                    Some(attributes) => attributes,
+                    None => {
+                        return Err(WebGPUSerializeError::new(
+                            ErrorKind::Unsupported,
+                            format!("invalid member attributes `{}`", name),
+                        ))
+                    }
+                };
+                let write = self.write;
+                let start = write.align(align);
+                let mut value_align = value.serialize(WebGPUSerializer {
+                    write: &mut *write,
+                    config: self.config,
+                })?;
+                if let Some(size) = size {
+                    // A larger member is reported when the layout is computed.
+                    write.fill(start, size, ErrorKind::Size);
+                }
+                value_align.append(align);
+                return Ok(value_align);
+            }
             "f16" => {
-                return value.serialize(WebGPUSerializer { write: self.write });
+                self.write.f16 = true;
+                return value.serialize(WebGPUSerializer {
+                    write: self.write,
+                    config: self.config,
+                });
+            }
+            "atomic@u32" | "atomic@i32" => {
+                if self.config.address_space == AddressSpace::Uniform {
+                    return Err(WebGPUSerializeError::new(
+                        ErrorKind::AddressSpace,
+                        "atomic is not supported in the uniform address space",
+                    )
+                    .with_type(match name {
+                        "atomic@u32" => "atomic<u32>",
+                        _ => "atomic<i32>",
+                    }));
+                }
+                let start = self.write.items.len();
+                self.write.leaf_depth += 1;
+                let align = value.serialize(WebGPUSerializer {
+                    write: &mut *self.write,
+                    config: self.config,
+                })?;
+                self.write.leaf_depth -= 1;
+                self.write.label_from(start, TagTypeName(name));
+                return Ok(align);
             }
             _ => {}
         }
@@ -295,25 +988,32 @@ impl<'s> Serializer for WebGPUSerializer<'s> {
         s.end()
     }
 
-    fn serialize_newtype_variant<T: ?Sized>(
+    fn serialize_newtype_variant<T>(
         self,
-        _name: &'static str,
-        _variant_index: u32,
-        _variant: &'static str,
-        _value: &T,
+        name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+        value: &T,
     ) -> Result<Self::Ok, Self::Error>
     where
-        T: Serialize,
+        T: ?Sized + Serialize,
     {
-        Err(serde::ser::Error::custom("enum is not supported"))
+        let mut s = self.serialize_variant(name, variant_index, variant)?;
+        s.serialize_element(value)?;
+        s.end()
     }
 
     fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
-        Ok(WebGPUSerializeStruct::new(self.write, Align::Align0))
+        if let Some(UnionContext::Names(_)) = self.write.union {
+            let mut s = WebGPUSerializeStruct::new(self.write, self.config, Align::Align0);
+            s.kind = StructKind::Names;
+            return Ok(s);
+        }
+        Ok(WebGPUSerializeStruct::new(self.write, self.config, Align::Align0).aggregate(true))
     }
 
     fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Self::Error> {
-        Ok(WebGPUSerializeStruct::new(self.write, Align::Align0))
+        Ok(WebGPUSerializeStruct::new(self.write, self.config, Align::Align0).aggregate(true))
     }
 
     fn serialize_tuple_struct(
@@ -321,102 +1021,447 @@ impl<'s> Serializer for WebGPUSerializer<'s> {
         name: &'static str,
         len: usize,
     ) -> Result<Self::SerializeTupleStruct, Self::Error> {
-        let vec_align = match len {
-            2 => match name {
-                "vec2@f16" => Some(Align::Align4),
-                "vec2@i32" | "vec2@u32" | "vec2@f32" => Some(Align::Align8),
-                _ => None,
-            },
-            3 => match name {
-                "vec3@f16" => Some(Align::Align8),
-                "vec3@i32" | "vec3@u32" | "vec3@f32" => Some(Align::Align16),
-                _ => None,
-            },
-            4 => match name {
-                "vec4@f16" => Some(Align::Align8),
-                "vec4@i32" | "vec4@u32" | "vec4@f32" => Some(Align::Align16),
-                _ => None,
-            },
-            _ => None,
-        };
-
-        if let Some(align) = vec_align {
-            return Ok(WebGPUSerializeStruct::new(self.write, align));
+        if name == "union" && len > 1 {
+            let mut s = WebGPUSerializeStruct::new(self.write, self.config, Align::Align0);
+            s.kind = StructKind::Union {
+                listed: false,
+                samples: len - 2,
+                layout: UnionLayout::default(),
+            };
+            return Ok(s);
         }
 
-        Ok(WebGPUSerializeStruct::new(self.write, Align::Align0))
+        let mut s = match vector_layout(name, len, self.config) {
+            Some((align, size)) => {
+                let mut s = WebGPUSerializeStruct::new(self.write, self.config, align);
+                match self.config.layout {
+                    LayoutMode::HlslCbuffer => s.write.register(size),
+                    // Unlike `packed_float3`, `float3` is padded to its alignment.
+                    LayoutMode::Msl if !name.starts_with("packed_") => s.min_align = align,
+                    _ => {}
+                }
+                s.leaf()
+            }
+            // Matrices are laid out as arrays of column vectors.
+            None if is_matrix(name) => {
+                WebGPUSerializeStruct::new(self.write, self.config, Align::Align0)
+                    .aggregate(true)
+                    .leaf()
+            }
+            None => {
+                WebGPUSerializeStruct::new(self.write, self.config, Align::Align0).aggregate(false)
+            }
+        };
+        s.name = Some(name);
+        Ok(s)
     }
 
     fn serialize_tuple_variant(
         self,
-        _name: &'static str,
-        _variant_index: u32,
-        _variant: &'static str,
+        name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
         _len: usize,
     ) -> Result<Self::SerializeTupleVariant, Self::Error> {
-        Err(serde::ser::Error::custom("enum is not supported"))
+        self.serialize_variant(name, variant_index, variant)
     }
 
     fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
-        Err(serde::ser::Error::custom("map is not supported"))
+        Err(WebGPUSerializeError::new(
+            ErrorKind::Unsupported,
+            "map is not supported",
+        ))
     }
 
     fn serialize_struct(
         self,
-        _name: &'static str,
+        name: &'static str,
         _len: usize,
     ) -> Result<Self::SerializeStruct, Self::Error> {
-        Ok(WebGPUSerializeStruct::new(self.write, Align::Align0))
+        let context = match self.write.union {
+            Some(UnionContext::Measure | UnionContext::Write(_)) => self.write.union.take(),
+            _ => None,
+        };
+        let mut s = WebGPUSerializeStruct::new(self.write, self.config, Align::Align0);
+        s.name = Some(name);
+        match context {
+            Some(context) => s.kind = StructKind::Tagged(context),
+            None => s = s.aggregate(false),
+        }
+        Ok(s)
     }
 
     fn serialize_struct_variant(
         self,
-        _name: &'static str,
-        _variant_index: u32,
-        _variant: &'static str,
+        name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
         _len: usize,
     ) -> Result<Self::SerializeStructVariant, Self::Error> {
-        Err(serde::ser::Error::custom("enum is not supported"))
+        self.serialize_variant(name, variant_index, variant)
+    }
+}
+
+impl<'s> WebGPUSerializer<'s> {
+    /// Begin the payload of an externally tagged variant of a union.
+    fn serialize_variant(
+        self,
+        name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+    ) -> Result<WebGPUSerializeStruct<'s>, WebGPUSerializeError> {
+        match self.write.union.take() {
+            Some(UnionContext::Measure) => {
+                self.write.variant = Some(VariantTag::Index(variant_index, variant));
+                Ok(WebGPUSerializeStruct::payload(
+                    self.write,
+                    self.config,
+                    Align::Align0,
+                    None,
+                ))
+            }
+            Some(UnionContext::Write(layout)) => {
+                if !layout.indices.contains(&variant_index) {
+                    return Err(WebGPUSerializeError::new(
+                        ErrorKind::Union,
+                        format!(
+                            "variant {} is missing from the union samples",
+                            variant_index
+                        ),
+                    )
+                    .with_type(name));
+                }
+                self.write.align(Align::Align4);
+                self.write.append(&u32::to_le_bytes(variant_index));
+                self.write.label("u32 (tag)");
+                Ok(WebGPUSerializeStruct::payload(
+                    self.write,
+                    self.config,
+                    layout.align,
+                    Some(layout.size),
+                ))
+            }
+            // The tag of `#[serde(tag = "...", content = "...")]` is a unit variant.
+            Some(UnionContext::Tag) => Err(WebGPUSerializeError::new(
+                ErrorKind::Union,
+                "adjacently tagged enum is not supported as a union, \
+                 use an externally or internally tagged enum",
+            )
+            .with_type(name)),
+            _ => Err(WebGPUSerializeError::new(
+                ErrorKind::Unsupported,
+                "enum with fields is only supported as a tagged union",
+            )
+            .with_type(name)),
+        }
     }
 }
 
+enum StructKind {
+    Plain,
+    /// A tagged union, whose first `samples` elements are variants measured for the payload
+    /// layout, followed by the value.
+    Union {
+        /// Whether the variant names, which come first, were read.
+        listed: bool,
+        samples: usize,
+        layout: UnionLayout,
+    },
+    /// The variant names of a union.
+    Names,
+    /// An internally tagged enum, whose first field is the tag.
+    Tagged(UnionContext),
+    /// The payload of a variant, filled up to the payload size of the union when writing.
+    Payload(Option<usize>),
+}
+
 struct WebGPUSerializeStruct<'s> {
     write: &'s mut WebGPUBlock,
+    config: &'s WebGPUConfig,
     align_index: usize,
+    member_align: Align,
     ext_align: Align,
+    kind: StructKind,
+    /// The Rust type name, when serde gives one.
+    name: Option<&'static str>,
+    /// The index of the next element, for the path of errors.
+    index: usize,
+    /// The minimum alignment of the layout mode, padded to at the end.
+    min_align: Align,
+    /// Every element is aligned to the element alignment of the layout mode.
+    array: bool,
+    /// A vector or a matrix, recorded as a single member of a layout dump.
+    leaf: bool,
 }
 
 impl<'s> WebGPUSerializeStruct<'s> {
     fn align_to(&mut self, align: Align) {
+        self.member_align.append(align);
         self.write.align_append(self.align_index, align);
     }
 }
 
 impl<'s> WebGPUSerializeStruct<'s> {
-    fn new(write: &'s mut WebGPUBlock, ext_align: Align) -> Self {
-        let align_index = write.align(Default::default());
+    fn new(write: &'s mut WebGPUBlock, config: &'s WebGPUConfig, ext_align: Align) -> Self {
+        // The start is aligned to the external alignment as well (e.g. `vec3<f32>` starts on
+        // 16 bytes), while the end is only padded to the member alignment (it stays 12 bytes).
+        let align_index = write.align(ext_align);
         Self {
             write,
+            config,
             align_index,
+            member_align: Align::Align0,
             ext_align,
+            kind: StructKind::Plain,
+            name: None,
+            index: 0,
+            min_align: Align::Align0,
+            array: false,
+            leaf: false,
         }
     }
 
-    fn serialize_element<T: ?Sized>(&mut self, value: &T) -> Result<(), WebGPUSerializeError>
+    /// Apply the minimum alignments of the layout mode to a struct or an array.
+    fn aggregate(mut self, array: bool) -> Self {
+        self.min_align = self.config.layout.aggregate_align();
+        self.write.align_append(self.align_index, self.min_align);
+        self.array = array;
+        self
+    }
+
+    fn leaf(mut self) -> Self {
+        self.write.leaf_depth += 1;
+        self.leaf = true;
+        self
+    }
+
+    fn payload(
+        write: &'s mut WebGPUBlock,
+        config: &'s WebGPUConfig,
+        align: Align,
+        size: Option<usize>,
+    ) -> Self {
+        let mut s = Self::new(write, config, align);
+        s.kind = StructKind::Payload(size);
+        s
+    }
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), WebGPUSerializeError>
     where
-        T: Serialize,
+        T: ?Sized + Serialize,
     {
-        let align = value.serialize(WebGPUSerializer { write: self.write })?;
+        match &mut self.kind {
+            StructKind::Union { listed, layout, .. } if !*listed => {
+                *listed = true;
+                let mut block = WebGPUBlock {
+                    union: Some(UnionContext::Names(Vec::new())),
+                    ..Default::default()
+                };
+                value.serialize(WebGPUSerializer {
+                    write: &mut block,
+                    config: self.config,
+                })?;
+                if let Some(UnionContext::Names(names)) = block.union {
+                    layout.variants = names;
+                }
+                return Ok(());
+            }
+            StructKind::Union {
+                samples, layout, ..
+            } if *samples > 0 => {
+                *samples -= 1;
+                let mut block = WebGPUBlock {
+                    union: Some(UnionContext::Measure),
+                    ..Default::default()
+                };
+                let align = value.serialize(WebGPUSerializer {
+                    write: &mut block,
+                    config: self.config,
+                })?;
+                match block.variant.take() {
+                    Some(VariantTag::Index(index, name)) => {
+                        // The listed names must agree with the tags of external samples.
+                        if !layout.variants.is_empty()
+                            && layout.variants.get(index as usize).map(String::as_str) != Some(name)
+                        {
+                            return Err(WebGPUSerializeError::new(
+                                ErrorKind::Union,
+                                format!("variant {} is not listed at {}", name, index),
+                            ));
+                        }
+                        layout.indices.push(index);
+                    }
+                    Some(VariantTag::Name(name)) => layout.names.push(name),
+                    None => {
+                        return Err(WebGPUSerializeError::new(
+                            ErrorKind::Union,
+                            "union sample is not an enum",
+                        ))
+                    }
+                }
+                layout.size = layout.size.max(block.compute_layout()?.len());
+                layout.align.append(align);
+                return Ok(());
+            }
+            StructKind::Union { layout, .. } => {
+                self.write.union = Some(UnionContext::Write(layout.clone()));
+                let align = value.serialize(WebGPUSerializer {
+                    write: self.write,
+                    config: self.config,
+                })?;
+                if self.write.union.take().is_some() {
+                    return Err(WebGPUSerializeError::new(
+                        ErrorKind::Union,
+                        "union value is not an enum",
+                    ));
+                }
+                self.align_to(Align::Align4);
+                self.align_to(align);
+                return Ok(());
+            }
+            StructKind::Tagged(context) => {
+                let context = context.clone();
+                let mut block = WebGPUBlock {
+                    union: Some(UnionContext::Tag),
+                    ..Default::default()
+                };
+                value.serialize(WebGPUSerializer {
+                    write: &mut block,
+                    config: self.config,
+                })?;
+                let name = match block.variant {
+                    Some(VariantTag::Name(name)) => name,
+                    _ => {
+                        return Err(WebGPUSerializeError::new(
+                            ErrorKind::Union,
+                            "tag of internally tagged enum is not a string",
+                        ))
+                    }
+                };
+                let (align, size) = match context {
+                    UnionContext::Write(layout) => {
+                        if !layout.names.contains(&name) {
+                            return Err(WebGPUSerializeError::new(
+                                ErrorKind::Union,
+                                format!("variant {} is missing from the union samples", name),
+                            ));
+                        }
+                        // The tag is the declaration index, as for externally tagged enums.
+                        let index = match layout.variants.iter().position(|i| *i == name) {
+                            Some(index) => index,
+                            None => {
+                                return Err(WebGPUSerializeError::new(
+                                    ErrorKind::Union,
+                                    format!("variant {} is not listed in the union variants", name),
+                                ))
+                            }
+                        };
+                        self.write.align(Align::Align4);
+                        self.write.append(&u32::to_le_bytes(index as u32));
+                        self.write.label("u32 (tag)");
+                        (layout.align, Some(layout.size))
+                    }
+                    _ => {
+                        self.write.variant = Some(VariantTag::Name(name));
+                        (Align::Align0, None)
+                    }
+                };
+                self.align_index = self.write.align(align);
+                self.ext_align = align;
+                self.kind = StructKind::Payload(size);
+                return Ok(());
+            }
+            _ => {}
+        }
+
+        if self.array {
+            let align = self.config.layout.element_align();
+            if align != Align::Align0 {
+                self.write.align(align);
+            }
+        }
+        let align = value.serialize(WebGPUSerializer {
+            write: self.write,
+            config: self.config,
+        })?;
         self.align_to(align);
         Ok(())
     }
 
+    /// Serialize a member by position, adding its index to the path of errors.
+    fn serialize_indexed<T>(&mut self, value: &T) -> Result<(), WebGPUSerializeError>
+    where
+        T: ?Sized + Serialize,
+    {
+        let index = self.index;
+        self.index += 1;
+        match self.kind {
+            // The samples and the value of a union are not members.
+            StructKind::Union { .. } => self.serialize_element(value),
+            _ => {
+                self.write.enter(|| format!("[{}]", index));
+                let result = self.serialize_element(value).map_err(|e| e.in_index(index));
+                self.write.leave();
+                result
+            }
+        }
+    }
+
+    /// Serialize a member by name, adding its key to the path of errors.
+    fn serialize_named<T>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), WebGPUSerializeError>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.write.enter(|| key.to_string());
+        let result = self.serialize_element(value).map_err(|e| e.in_field(key));
+        self.write.leave();
+        result
+    }
+
     fn end(self) -> Result<Align, WebGPUSerializeError> {
-        let align = self.write.get_align(self.align_index);
-        if align == Align::Align0 {
-            return Err(serde::ser::Error::custom("zero size type is not supported"));
+        match self.kind {
+            StructKind::Payload(size) => {
+                if self.member_align != Align::Align0 {
+                    self.write.align(self.member_align);
+                }
+                if let Some(size) = size {
+                    self.write.fill(self.align_index, size, ErrorKind::Union);
+                }
+                return Ok(self.member_align.with(self.ext_align));
+            }
+            StructKind::Tagged(_) => {
+                return Err(WebGPUSerializeError::new(
+                    ErrorKind::Union,
+                    "internally tagged enum has no tag",
+                ));
+            }
+            StructKind::Names => return Ok(Align::Align0),
+            _ => {}
+        }
+
+        if self.member_align == Align::Align0 {
+            let error =
+                WebGPUSerializeError::new(ErrorKind::ZeroSize, "zero size type is not supported");
+            return Err(match self.name {
+                Some(name) => error.with_type(name),
+                None => error,
+            });
+        }
+        let align = self.member_align.with(self.min_align);
+        if self.array && !self.config.layout.pads_arrays() {
+            self.write.align(self.member_align);
+        } else {
+            self.write.align(align);
+        }
+        if self.leaf {
+            self.write.leaf_depth -= 1;
+            self.write
+                .label_from(self.align_index, TagTypeName(self.name.unwrap()));
         }
-        self.write.align(align);
         Ok(align.with(self.ext_align))
     }
 }
@@ -425,11 +1470,11 @@ impl<'s> SerializeTuple for WebGPUSerializeStruct<'s> {
     type Ok = <WebGPUSerializer<'s> as Serializer>::Ok;
     type Error = <WebGPUSerializer<'s> as Serializer>::Error;
 
-    fn serialize_element<T: ?Sized>(&mut self, value: &T) -> Result<(), Self::Error>
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), Self::Error>
     where
-        T: Serialize,
+        T: ?Sized + Serialize,
     {
-        self.serialize_element(value)
+        self.serialize_indexed(value)
     }
 
     fn end(self) -> Result<Self::Ok, Self::Error> {
@@ -441,11 +1486,11 @@ impl<'s> SerializeTupleStruct for WebGPUSerializeStruct<'s> {
     type Ok = <WebGPUSerializer<'s> as Serializer>::Ok;
     type Error = <WebGPUSerializer<'s> as Serializer>::Error;
 
-    fn serialize_field<T: ?Sized>(&mut self, value: &T) -> Result<(), Self::Error>
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), Self::Error>
     where
-        T: Serialize,
+        T: ?Sized + Serialize,
     {
-        self.serialize_element(value)
+        self.serialize_indexed(value)
     }
 
     fn end(self) -> Result<Self::Ok, Self::Error> {
@@ -457,15 +1502,11 @@ impl<'s> SerializeStruct for WebGPUSerializeStruct<'s> {
     type Ok = <WebGPUSerializer<'s> as Serializer>::Ok;
     type Error = <WebGPUSerializer<'s> as Serializer>::Error;
 
-    fn serialize_field<T: ?Sized>(
-        &mut self,
-        _key: &'static str,
-        value: &T,
-    ) -> Result<(), Self::Error>
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), Self::Error>
     where
-        T: Serialize,
+        T: ?Sized + Serialize,
     {
-        self.serialize_element(value)
+        self.serialize_named(key, value)
     }
 
     fn end(self) -> Result<Self::Ok, Self::Error> {
@@ -477,11 +1518,43 @@ impl<'s> SerializeSeq for WebGPUSerializeStruct<'s> {
     type Ok = <WebGPUSerializer<'s> as Serializer>::Ok;
     type Error = <WebGPUSerializer<'s> as Serializer>::Error;
 
-    fn serialize_element<T: ?Sized>(&mut self, value: &T) -> Result<(), Self::Error>
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.serialize_indexed(value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        self.end()
+    }
+}
+
+impl<'s> SerializeTupleVariant for WebGPUSerializeStruct<'s> {
+    type Ok = <WebGPUSerializer<'s> as Serializer>::Ok;
+    type Error = <WebGPUSerializer<'s> as Serializer>::Error;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.serialize_indexed(value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        self.end()
+    }
+}
+
+impl<'s> SerializeStructVariant for WebGPUSerializeStruct<'s> {
+    type Ok = <WebGPUSerializer<'s> as Serializer>::Ok;
+    type Error = <WebGPUSerializer<'s> as Serializer>::Error;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), Self::Error>
     where
-        T: Serialize,
+        T: ?Sized + Serialize,
     {
-        self.serialize_element(value)
+        self.serialize_named(key, value)
     }
 
     fn end(self) -> Result<Self::Ok, Self::Error> {
@@ -489,20 +1562,58 @@ impl<'s> SerializeSeq for WebGPUSerializeStruct<'s> {
     }
 }
 
-fn serialize_webgpu_base<T: Serialize>(value: &T) -> Result<WebGPUBlock, WebGPUSerializeError> {
-    let mut block = WebGPUBlock::default();
-    let serializer = WebGPUSerializer { write: &mut block };
-    value.serialize(serializer)?;
-    Ok(block)
+fn serialize_webgpu_base<T: Serialize>(
+    value: &T,
+    config: &WebGPUConfig,
+) -> Result<(WebGPUBlock, Align), WebGPUSerializeError> {
+    serialize_webgpu_block(value, config, WebGPUBlock::default())
+}
+
+fn serialize_webgpu_block<T: Serialize>(
+    value: &T,
+    config: &WebGPUConfig,
+    mut block: WebGPUBlock,
+) -> Result<(WebGPUBlock, Align), WebGPUSerializeError> {
+    let serializer = WebGPUSerializer {
+        write: &mut block,
+        config,
+    };
+    match value.serialize(serializer) {
+        Ok(align) => Ok((block, align)),
+        Err(e) => Err(e.at_offset(block.size())),
+    }
+}
+
+/// The size and alignment of the serialized value.
+fn serialize_webgpu_layout<T: Serialize>(
+    value: &T,
+    config: &WebGPUConfig,
+) -> Result<(usize, usize), WebGPUSerializeError> {
+    let (block, align) = serialize_webgpu_base(value, config)?;
+    Ok((block.compute_layout()?.len(), align.value()))
 }
 
 pub fn serialize_webgpu<T: Serialize>(value: &T) -> Result<Vec<u8>, WebGPUSerializeError> {
-    let block = serialize_webgpu_base(value)?;
-    Ok(block.compute_layout())
+    serialize_webgpu_with(value, &WebGPUConfig::default())
+}
+
+pub fn serialize_webgpu_with<T: Serialize>(
+    value: &T,
+    config: &WebGPUConfig,
+) -> Result<Vec<u8>, WebGPUSerializeError> {
+    let (block, _) = serialize_webgpu_base(value, config)?;
+    block.compute_layout()
 }
 
 pub fn serialize_webgpu_buffer<T: Serialize>(value: &T) -> Result<Vec<u8>, WebGPUSerializeError> {
-    let mut block = serialize_webgpu_base(value)?;
+    serialize_webgpu_buffer_with(value, &WebGPUConfig::default())
+}
+
+pub fn serialize_webgpu_buffer_with<T: Serialize>(
+    value: &T,
+    config: &WebGPUConfig,
+) -> Result<Vec<u8>, WebGPUSerializeError> {
+    let (mut block, _) = serialize_webgpu_base(value, config)?;
     block.align(Align::Align16);
-    Ok(block.compute_layout())
+    block.compute_layout()
 }
@@ -1,15 +1,250 @@
 #![allow(non_camel_case_types)]
 
+use std::ops::{Index, IndexMut, Mul};
+
+use serde::ser::SerializeTupleStruct;
+use serde::{Serialize, Serializer};
+
+use crate::f16;
+use crate::layout::WgslLayout;
+use crate::scalar::Float;
 use crate::vec::{vec2, vec3, vec4};
 
-pub type mat2x2<T> = [vec2<T>; 2];
-pub type mat3x2<T> = [vec2<T>; 3];
-pub type mat4x2<T> = [vec2<T>; 4];
+macro_rules! matrix {
+    ($name:ident, $transpose:ident, $col:ident, $row:ident, $c:literal, $r:literal) => {
+        /// A column-major matrix of `C` columns and `R` rows.
+        #[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+        pub struct $name<T>(pub [$col<T>; $c]);
+
+        impl<T> $name<T> {
+            pub const fn from_cols(cols: [$col<T>; $c]) -> Self {
+                Self(cols)
+            }
+        }
+
+        impl<T: Copy> $name<T> {
+            pub fn from_rows(rows: [$row<T>; $r]) -> Self {
+                Self(std::array::from_fn(|c| {
+                    $col(std::array::from_fn(|r| rows[r][c]))
+                }))
+            }
+
+            pub fn col(&self, c: usize) -> $col<T> {
+                self.0[c]
+            }
+
+            pub fn row(&self, r: usize) -> $row<T> {
+                $row(std::array::from_fn(|c| self.0[c][r]))
+            }
+
+            pub fn transpose(&self) -> $transpose<T> {
+                $transpose(std::array::from_fn(|r| self.row(r)))
+            }
+        }
+
+        impl<T> From<[[T; $r]; $c]> for $name<T> {
+            fn from(value: [[T; $r]; $c]) -> Self {
+                Self(value.map($col))
+            }
+        }
+
+        impl<T> From<$name<T>> for [[T; $r]; $c] {
+            fn from(value: $name<T>) -> Self {
+                value.0.map(|col| col.0)
+            }
+        }
+
+        impl<T> From<[$col<T>; $c]> for $name<T> {
+            fn from(value: [$col<T>; $c]) -> Self {
+                Self(value)
+            }
+        }
+
+        impl<T> Index<usize> for $name<T> {
+            type Output = $col<T>;
+
+            fn index(&self, index: usize) -> &Self::Output {
+                &self.0[index]
+            }
+        }
+
+        impl<T> IndexMut<usize> for $name<T> {
+            fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+                &mut self.0[index]
+            }
+        }
+
+        impl<T: Float> Mul<$row<T>> for $name<T> {
+            type Output = $col<T>;
+
+            fn mul(self, rhs: $row<T>) -> Self::Output {
+                $col(std::array::from_fn(|r| {
+                    (0..$c).fold(T::ZERO, |sum, c| sum + self.0[c][r] * rhs[c])
+                }))
+            }
+        }
+
+        impl<T: Float> Mul<T> for $name<T> {
+            type Output = Self;
+
+            fn mul(self, rhs: T) -> Self::Output {
+                Self(self.0.map(|col| $col(col.0.map(|v| v * rhs))))
+            }
+        }
+
+        matrix_serialize!($name, $col, $c, f16, f32, f64);
+    };
+}
+
+macro_rules! matrix_serialize {
+    ($name:ident, $col:ident, $c:literal, $($t:ident),*) => {
+        $(
+            impl WgslLayout for $name<$t> {
+                const SIZE: usize = $c * <$col<$t>>::STRIDE;
+                const ALIGN: usize = <$col<$t>>::ALIGN;
+                const WGSL_TYPE_NAME: &'static str =
+                    concat!(stringify!($name), "<", stringify!($t), ">");
+            }
+
+            impl Serialize for $name<$t> {
+                fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+                where
+                    S: Serializer,
+                {
+                    let name = concat!(stringify!($name), "@", stringify!($t));
+                    let mut s = serializer.serialize_tuple_struct(name, $c)?;
+                    for col in &self.0 {
+                        s.serialize_field(col)?;
+                    }
+                    s.end()
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! matrix_square {
+    ($name:ident, $col:ident, $n:literal) => {
+        impl<T: Float> $name<T> {
+            pub fn identity() -> Self {
+                Self(std::array::from_fn(|c| {
+                    $col(std::array::from_fn(
+                        |r| if c == r { T::ONE } else { T::ZERO },
+                    ))
+                }))
+            }
+
+            pub fn determinant(&self) -> T {
+                match eliminate(self.0.map(|col| col.0), [[T::ZERO; $n]; $n]) {
+                    Some((determinant, _)) => determinant,
+                    None => T::ZERO,
+                }
+            }
+
+            /// The inverse, or `None` for a singular matrix.
+            pub fn inverse(&self) -> Option<Self> {
+                let identity = Self::identity().0.map(|col| col.0);
+                eliminate(self.0.map(|col| col.0), identity)
+                    .map(|(_, inverse)| Self(inverse.map($col)))
+            }
+        }
+    };
+}
+
+macro_rules! matrix_mul {
+    ($($lhs:ident * $rhs:ident = $output:ident),* $(,)?) => {
+        $(
+            impl<T: Float> Mul<$rhs<T>> for $lhs<T> {
+                type Output = $output<T>;
+
+                fn mul(self, rhs: $rhs<T>) -> Self::Output {
+                    $output(rhs.0.map(|col| self * col))
+                }
+            }
+        )*
+    };
+}
+
+/// Gauss-Jordan elimination with partial pivoting, which applies the row operations reducing the
+/// columns `m` to the identity on the columns `rhs` as well, and tracks the determinant.
+fn eliminate<T: Float, const N: usize>(
+    mut m: [[T; N]; N],
+    mut rhs: [[T; N]; N],
+) -> Option<(T, [[T; N]; N])> {
+    let mut determinant = T::ONE;
+    for i in 0..N {
+        let pivot = (i..N).fold(i, |p, r| if m[i][r].abs() > m[i][p].abs() { r } else { p });
+        if m[i][pivot] == T::ZERO {
+            return None;
+        }
+        if pivot != i {
+            for c in 0..N {
+                m[c].swap(i, pivot);
+                rhs[c].swap(i, pivot);
+            }
+            determinant = -determinant;
+        }
+        let p = m[i][i];
+        determinant = determinant * p;
+        for c in 0..N {
+            m[c][i] = m[c][i] / p;
+            rhs[c][i] = rhs[c][i] / p;
+        }
+        for r in 0..N {
+            if r != i {
+                let f = m[i][r];
+                for c in 0..N {
+                    m[c][r] = m[c][r] - f * m[c][i];
+                    rhs[c][r] = rhs[c][r] - f * rhs[c][i];
+                }
+            }
+        }
+    }
+    Some((determinant, rhs))
+}
+
+matrix!(mat2x2, mat2x2, vec2, vec2, 2, 2);
+matrix!(mat3x2, mat2x3, vec2, vec3, 3, 2);
+matrix!(mat4x2, mat2x4, vec2, vec4, 4, 2);
+
+matrix!(mat2x3, mat3x2, vec3, vec2, 2, 3);
+matrix!(mat3x3, mat3x3, vec3, vec3, 3, 3);
+matrix!(mat4x3, mat3x4, vec3, vec4, 4, 3);
+
+matrix!(mat2x4, mat4x2, vec4, vec2, 2, 4);
+matrix!(mat3x4, mat4x3, vec4, vec3, 3, 4);
+matrix!(mat4x4, mat4x4, vec4, vec4, 4, 4);
 
-pub type mat2x3<T> = [vec3<T>; 2];
-pub type mat3x3<T> = [vec3<T>; 3];
-pub type mat4x3<T> = [vec3<T>; 4];
+matrix_square!(mat2x2, vec2, 2);
+matrix_square!(mat3x3, vec3, 3);
+matrix_square!(mat4x4, vec4, 4);
 
-pub type mat2x4<T> = [vec4<T>; 2];
-pub type mat3x4<T> = [vec4<T>; 3];
-pub type mat4x4<T> = [vec4<T>; 4];
+matrix_mul!(
+    mat2x2 * mat2x2 = mat2x2,
+    mat2x2 * mat3x2 = mat3x2,
+    mat2x2 * mat4x2 = mat4x2,
+    mat3x2 * mat2x3 = mat2x2,
+    mat3x2 * mat3x3 = mat3x2,
+    mat3x2 * mat4x3 = mat4x2,
+    mat4x2 * mat2x4 = mat2x2,
+    mat4x2 * mat3x4 = mat3x2,
+    mat4x2 * mat4x4 = mat4x2,
+    mat2x3 * mat2x2 = mat2x3,
+    mat2x3 * mat3x2 = mat3x3,
+    mat2x3 * mat4x2 = mat4x3,
+    mat3x3 * mat2x3 = mat2x3,
+    mat3x3 * mat3x3 = mat3x3,
+    mat3x3 * mat4x3 = mat4x3,
+    mat4x3 * mat2x4 = mat2x3,
+    mat4x3 * mat3x4 = mat3x3,
+    mat4x3 * mat4x4 = mat4x3,
+    mat2x4 * mat2x2 = mat2x4,
+    mat2x4 * mat3x2 = mat3x4,
+    mat2x4 * mat4x2 = mat4x4,
+    mat3x4 * mat2x3 = mat2x4,
+    mat3x4 * mat3x3 = mat3x4,
+    mat3x4 * mat4x3 = mat4x4,
+    mat4x4 * mat2x4 = mat2x4,
+    mat4x4 * mat3x4 = mat3x4,
+    mat4x4 * mat4x4 = mat4x4,
+);
@@ -0,0 +1,208 @@
+//! Build vertex and index buffers from a stream of vertices.
+//!
+//! Vertices are welded by their serialized bytes, so two vertices are merged exactly when the GPU
+//! would see the same data.
+//!
+//! ```
+//! # use serde::Serialize;
+//! # use serde_webgpu::mesh::{IndexBuffer, MeshBuilder};
+//! # use serde_webgpu::vec::{vec2, vec3};
+//! #[derive(Serialize)]
+//! struct Vertex {
+//!     position: vec3<f32>,
+//!     uv: vec2<f32>,
+//! }
+//!
+//! let mut builder = MeshBuilder::new();
+//! for [x, y] in [[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 0.0], [1.0, 1.0], [0.0, 1.0]] {
+//!     builder.push(&Vertex {
+//!         position: vec3([x, y, 0.0]),
+//!         uv: vec2([x, y]),
+//!     }).unwrap();
+//! }
+//!
+//! let mesh = builder.build().unwrap();
+//! assert_eq!(mesh.vertex_count, 4);
+//! assert_eq!(mesh.vertex_stride, 32);
+//! assert_eq!(mesh.indices, IndexBuffer::Uint16(vec![0, 1, 2, 0, 2, 3]));
+//! ```
+//!
+
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use crate::{serialize_webgpu, ErrorKind, WebGPUSerializeError};
+
+/// Index buffer with the smallest format able to address every vertex.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum IndexBuffer {
+    Uint16(Vec<u16>),
+    Uint32(Vec<u32>),
+}
+
+impl IndexBuffer {
+    pub fn len(&self) -> usize {
+        match self {
+            IndexBuffer::Uint16(i) => i.len(),
+            IndexBuffer::Uint32(i) => i.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The primitive restart value of this index format.
+    pub fn restart_value(&self) -> u32 {
+        match self {
+            IndexBuffer::Uint16(_) => u16::MAX as u32,
+            IndexBuffer::Uint32(_) => u32::MAX,
+        }
+    }
+
+    /// The little endian index data, padded to a multiple of 4 bytes as required by `writeBuffer`.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut buffer = Vec::new();
+        match self {
+            IndexBuffer::Uint16(i) => i
+                .iter()
+                .for_each(|i| buffer.extend_from_slice(&i.to_le_bytes())),
+            IndexBuffer::Uint32(i) => i
+                .iter()
+                .for_each(|i| buffer.extend_from_slice(&i.to_le_bytes())),
+        }
+        buffer.resize((buffer.len() + 3) & !3, 0);
+        buffer
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Mesh {
+    pub vertices: Vec<u8>,
+    pub vertex_stride: usize,
+    pub vertex_count: u32,
+    pub indices: IndexBuffer,
+    pub primitive_restart: bool,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct MeshBuilder {
+    vertices: Vec<u8>,
+    vertex_stride: Option<usize>,
+    vertex_count: u32,
+    welded: HashMap<Vec<u8>, u32>,
+    indices: Vec<Option<u32>>,
+}
+
+impl MeshBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn vertex_count(&self) -> u32 {
+        self.vertex_count
+    }
+
+    /// Append a vertex, returning the index of the (possibly welded) vertex.
+    pub fn push<T: Serialize>(&mut self, vertex: &T) -> Result<u32, WebGPUSerializeError> {
+        let mut bytes = serialize_webgpu(vertex)?;
+        // `arrayStride` must be a multiple of 4.
+        bytes.resize((bytes.len() + 3) & !3, 0);
+
+        match self.vertex_stride {
+            None => self.vertex_stride = Some(bytes.len()),
+            Some(stride) if stride != bytes.len() => {
+                return Err(WebGPUSerializeError::new(
+                    ErrorKind::Size,
+                    format!(
+                        "vertex size {} does not match the vertex stride {}",
+                        bytes.len(),
+                        stride
+                    ),
+                ));
+            }
+            Some(_) => {}
+        }
+
+        let index = match self.welded.get(&bytes) {
+            Some(&index) => index,
+            None => {
+                // `u32::MAX` is reserved for primitive restart.
+                if self.vertex_count == u32::MAX {
+                    return Err(WebGPUSerializeError::new(
+                        ErrorKind::Capacity,
+                        "too many vertices",
+                    ));
+                }
+                let index = self.vertex_count;
+                self.vertex_count += 1;
+                self.vertices.extend_from_slice(&bytes);
+                self.welded.insert(bytes, index);
+                index
+            }
+        };
+        self.indices.push(Some(index));
+        Ok(index)
+    }
+
+    pub fn extend<T, I>(&mut self, vertices: I) -> Result<(), WebGPUSerializeError>
+    where
+        T: Serialize,
+        I: IntoIterator<Item = T>,
+    {
+        for vertex in vertices {
+            self.push(&vertex)?;
+        }
+        Ok(())
+    }
+
+    /// Insert a primitive restart, which starts a new strip.
+    pub fn restart(&mut self) {
+        self.indices.push(None);
+    }
+
+    pub fn build(self) -> Result<Mesh, WebGPUSerializeError> {
+        let primitive_restart = self.indices.iter().any(Option::is_none);
+        let vertex_stride = match self.vertex_stride {
+            Some(stride) => stride,
+            None => {
+                return Err(WebGPUSerializeError::new(
+                    ErrorKind::Size,
+                    "mesh has no vertex",
+                ))
+            }
+        };
+
+        // With primitive restart, the restart value itself cannot be used as an index.
+        let uint16_limit = if primitive_restart { 0xFFFF } else { 0x10000 };
+        let indices = if self.vertex_count <= uint16_limit {
+            IndexBuffer::Uint16(
+                self.indices
+                    .iter()
+                    .map(|i| i.map_or(u16::MAX, |i| i as u16))
+                    .collect(),
+            )
+        } else {
+            IndexBuffer::Uint32(self.indices.iter().map(|i| i.unwrap_or(u32::MAX)).collect())
+        };
+
+        Ok(Mesh {
+            vertices: self.vertices,
+            vertex_stride,
+            vertex_count: self.vertex_count,
+            indices,
+            primitive_restart,
+        })
+    }
+}
+
+pub fn build_mesh<T, I>(vertices: I) -> Result<Mesh, WebGPUSerializeError>
+where
+    T: Serialize,
+    I: IntoIterator<Item = T>,
+{
+    let mut builder = MeshBuilder::new();
+    builder.extend(vertices)?;
+    builder.build()
+}
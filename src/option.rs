@@ -0,0 +1,163 @@
+//! Fixed layouts for `Option<T>`, which stay the same whether the value is `Some` or `None`.
+//!
+//! ```
+//! # use serde::Serialize;
+//! # use serde_webgpu::option::{OptionFlag, OptionSentinel};
+//! # use serde_webgpu::serialize_webgpu;
+//! # use serde_webgpu::vec::vec2;
+//! #[derive(Serialize)]
+//! struct Params {
+//!     #[serde(serialize_with = "serde_webgpu::option::flag")]
+//!     clip: Option<vec2<f32>>,
+//!     max_distance: OptionSentinel<f32>,
+//! }
+//!
+//! let params = Params {
+//!     clip: Some(vec2([0.0, 1.0])),
+//!     max_distance: OptionSentinel(None),
+//! };
+//! let buffer = serialize_webgpu(&params).unwrap();
+//! assert_eq!(buffer[..4], [1, 0, 0, 0]);
+//! assert!(f32::from_le_bytes(buffer[16..20].try_into().unwrap()).is_nan());
+//!
+//! let empty = serialize_webgpu(&OptionFlag::<vec2<f32>>(None)).unwrap();
+//! assert_eq!(empty, [0; 16]);
+//! ```
+//!
+
+use serde::ser::SerializeTupleStruct;
+use serde::{Serialize, Serializer};
+
+use crate::f16;
+use crate::vec::Vector;
+
+/// The value standing for `None`.
+pub trait Sentinel {
+    fn sentinel() -> Self;
+}
+
+impl Sentinel for f16 {
+    fn sentinel() -> Self {
+        f16::NAN
+    }
+}
+
+impl Sentinel for f32 {
+    fn sentinel() -> Self {
+        f32::NAN
+    }
+}
+
+impl Sentinel for i32 {
+    fn sentinel() -> Self {
+        i32::MIN
+    }
+}
+
+impl Sentinel for u32 {
+    fn sentinel() -> Self {
+        u32::MAX
+    }
+}
+
+impl<T: Sentinel, const N: usize> Sentinel for Vector<T, N> {
+    fn sentinel() -> Self {
+        Vector(std::array::from_fn(|_| T::sentinel()))
+    }
+}
+
+/// Serialize an `u32` presence flag followed by the value, or its default for `None`.
+pub fn flag<T, S>(value: &Option<T>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: Serialize + Default,
+    S: Serializer,
+{
+    let mut s = serializer.serialize_tuple_struct("OptionFlag", 2)?;
+    match value {
+        Some(value) => {
+            s.serialize_field(&1u32)?;
+            s.serialize_field(value)?;
+        }
+        None => {
+            s.serialize_field(&0u32)?;
+            s.serialize_field(&T::default())?;
+        }
+    }
+    s.end()
+}
+
+/// Serialize the value, or [`Sentinel::sentinel`] for `None`.
+pub fn sentinel<T, S>(value: &Option<T>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: Serialize + Sentinel,
+    S: Serializer,
+{
+    match value {
+        Some(value) => value.serialize(serializer),
+        None => T::sentinel().serialize(serializer),
+    }
+}
+
+/// Serialize the value, or zeros in the layout of the default value for `None`.
+///
+/// The member keeps its place and size either way. There is no flag, so the shader must learn
+/// from elsewhere, such as a count, whether the value is present.
+pub fn zeroed<T, S>(value: &Option<T>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: Serialize + Default,
+    S: Serializer,
+{
+    match value {
+        Some(value) => value.serialize(serializer),
+        None => serializer.serialize_newtype_struct("option@zeroed", &T::default()),
+    }
+}
+
+/// Serialize the value, or the given sentinel for `None`.
+pub fn sentinel_with<T, S>(
+    value: &Option<T>,
+    sentinel: &T,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    T: Serialize,
+    S: Serializer,
+{
+    value.as_ref().unwrap_or(sentinel).serialize(serializer)
+}
+
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct OptionFlag<T>(pub Option<T>);
+
+impl<T: Serialize + Default> Serialize for OptionFlag<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        flag(&self.0, serializer)
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct OptionSentinel<T>(pub Option<T>);
+
+impl<T: Serialize + Sentinel> Serialize for OptionSentinel<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        sentinel(&self.0, serializer)
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct OptionZeroed<T>(pub Option<T>);
+
+impl<T: Serialize + Default> Serialize for OptionZeroed<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        zeroed(&self.0, serializer)
+    }
+}
@@ -0,0 +1,207 @@
+//! The WGSL `pack*` and `unpack*` builtins, which squeeze vectors into an `u32`.
+//!
+//! The functions match the builtins bit for bit, and the types hold the packed `u32` to serialize
+//! it as one.
+//!
+//! ```
+//! # use serde::Serialize;
+//! # use serde_webgpu::pack::{unpack4x8unorm, Snorm2x16, Unorm4x8};
+//! # use serde_webgpu::serialize_webgpu;
+//! # use serde_webgpu::vec::{vec2, vec4};
+//! #[derive(Serialize)]
+//! struct Vertex {
+//!     color: Unorm4x8,
+//!     normal: Snorm2x16,
+//! }
+//!
+//! let vertex = Vertex {
+//!     color: Unorm4x8::new(vec4([1.0, 0.5, 0.0, 1.0])),
+//!     normal: Snorm2x16::new(vec2([0.0, -1.0])),
+//! };
+//! let buffer = serialize_webgpu(&vertex).unwrap();
+//! assert_eq!(buffer, [255, 128, 0, 255, 0, 0, 1, 128]);
+//! assert_eq!(unpack4x8unorm(vertex.color.0), vec4([1.0, 128.0 / 255.0, 0.0, 1.0]));
+//! ```
+//!
+
+use serde::{Serialize, Serializer};
+
+use crate::f16;
+use crate::layout::WgslLayout;
+use crate::vec::{vec2, vec4, Vector};
+
+fn pack<const N: usize>(bits: u32, v: Vector<u32, N>) -> u32 {
+    let mask = u32::MAX >> (32 - bits);
+    (0..N).fold(0, |packed, i| packed | (v[i] & mask) << (bits * i as u32))
+}
+
+fn unpack<const N: usize>(bits: u32, packed: u32) -> Vector<u32, N> {
+    let mask = u32::MAX >> (32 - bits);
+    Vector(std::array::from_fn(|i| packed >> (bits * i as u32) & mask))
+}
+
+/// Sign extend the low `bits` bits.
+fn sign_extend(bits: u32, v: u32) -> i32 {
+    ((v << (32 - bits)) as i32) >> (32 - bits)
+}
+
+fn snorm(max: f32, e: f32) -> u32 {
+    (0.5 + max * e.clamp(-1.0, 1.0)).floor() as i32 as u32
+}
+
+fn unorm(max: f32, e: f32) -> u32 {
+    (0.5 + max * e.clamp(0.0, 1.0)).floor() as u32
+}
+
+pub fn pack4x8snorm(e: vec4<f32>) -> u32 {
+    pack(8, Vector(e.0.map(|e| snorm(127.0, e))))
+}
+
+pub fn unpack4x8snorm(e: u32) -> vec4<f32> {
+    Vector(
+        unpack(8, e)
+            .0
+            .map(|v| (sign_extend(8, v) as f32 / 127.0).max(-1.0)),
+    )
+}
+
+pub fn pack4x8unorm(e: vec4<f32>) -> u32 {
+    pack(8, Vector(e.0.map(|e| unorm(255.0, e))))
+}
+
+pub fn unpack4x8unorm(e: u32) -> vec4<f32> {
+    Vector(unpack(8, e).0.map(|v| v as f32 / 255.0))
+}
+
+pub fn pack2x16snorm(e: vec2<f32>) -> u32 {
+    pack(16, Vector(e.0.map(|e| snorm(32767.0, e))))
+}
+
+pub fn unpack2x16snorm(e: u32) -> vec2<f32> {
+    Vector(
+        unpack(16, e)
+            .0
+            .map(|v| (sign_extend(16, v) as f32 / 32767.0).max(-1.0)),
+    )
+}
+
+pub fn pack2x16unorm(e: vec2<f32>) -> u32 {
+    pack(16, Vector(e.0.map(|e| unorm(65535.0, e))))
+}
+
+pub fn unpack2x16unorm(e: u32) -> vec2<f32> {
+    Vector(unpack(16, e).0.map(|v| v as f32 / 65535.0))
+}
+
+/// Round to the nearest `f16`, where WGSL leaves values out of the `f16` range indeterminate.
+pub fn pack2x16float(e: vec2<f32>) -> u32 {
+    pack(16, Vector(e.0.map(|e| f16::from_f32(e).to_bits() as u32)))
+}
+
+pub fn unpack2x16float(e: u32) -> vec2<f32> {
+    Vector(unpack(16, e).0.map(|v| f16::from_bits(v as u16).to_f32()))
+}
+
+/// `pack4xI8`, which keeps the low 8 bits of each component.
+pub fn pack4xi8(e: vec4<i32>) -> u32 {
+    pack(8, Vector(e.0.map(|e| e as u32)))
+}
+
+/// `pack4xI8Clamp`.
+pub fn pack4xi8_clamp(e: vec4<i32>) -> u32 {
+    pack(8, Vector(e.0.map(|e| e.clamp(-128, 127) as u32)))
+}
+
+/// `unpack4xI8`.
+pub fn unpack4xi8(e: u32) -> vec4<i32> {
+    Vector(unpack(8, e).0.map(|v| sign_extend(8, v)))
+}
+
+/// `pack4xU8`, which keeps the low 8 bits of each component.
+pub fn pack4xu8(e: vec4<u32>) -> u32 {
+    pack(8, e)
+}
+
+/// `pack4xU8Clamp`.
+pub fn pack4xu8_clamp(e: vec4<u32>) -> u32 {
+    pack(8, Vector(e.0.map(|e| e.min(255))))
+}
+
+/// `unpack4xU8`.
+pub fn unpack4xu8(e: u32) -> vec4<u32> {
+    unpack(8, e)
+}
+
+macro_rules! packed {
+    ($(#[$doc:meta])* $name:ident, $vector:ty, $pack:ident, $unpack:ident) => {
+        $(#[$doc])*
+        #[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+        pub struct $name(pub u32);
+
+        impl $name {
+            pub fn new(value: $vector) -> Self {
+                Self($pack(value))
+            }
+
+            pub fn get(self) -> $vector {
+                $unpack(self.0)
+            }
+        }
+
+        impl From<$vector> for $name {
+            fn from(value: $vector) -> Self {
+                Self::new(value)
+            }
+        }
+
+        impl From<$name> for $vector {
+            fn from(value: $name) -> Self {
+                value.get()
+            }
+        }
+
+        impl WgslLayout for $name {
+            const SIZE: usize = 4;
+            const ALIGN: usize = 4;
+            const WGSL_TYPE_NAME: &'static str = "u32";
+        }
+
+        impl Serialize for $name {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+            {
+                serializer.serialize_u32(self.0)
+            }
+        }
+    };
+}
+
+packed!(
+    /// A `vec4<f32>` unpacked by `unpack4x8snorm`.
+    Snorm4x8, vec4<f32>, pack4x8snorm, unpack4x8snorm
+);
+packed!(
+    /// A `vec4<f32>` unpacked by `unpack4x8unorm`.
+    Unorm4x8, vec4<f32>, pack4x8unorm, unpack4x8unorm
+);
+packed!(
+    /// A `vec2<f32>` unpacked by `unpack2x16snorm`.
+    Snorm2x16, vec2<f32>, pack2x16snorm, unpack2x16snorm
+);
+packed!(
+    /// A `vec2<f32>` unpacked by `unpack2x16unorm`.
+    Unorm2x16, vec2<f32>, pack2x16unorm, unpack2x16unorm
+);
+packed!(
+    /// A `vec2<f32>` unpacked by `unpack2x16float`.
+    Float2x16, vec2<f32>, pack2x16float, unpack2x16float
+);
+packed!(
+    /// A `vec4<i32>` unpacked by `unpack4xI8`.
+    I8x4, vec4<i32>, pack4xi8, unpack4xi8
+);
+packed!(
+    /// A `vec4<u32>` unpacked by `unpack4xU8`.
+    U8x4, vec4<u32>, pack4xu8, unpack4xu8
+);
@@ -0,0 +1,115 @@
+//! Packed vectors, such as the Metal `packed_float3`, which are aligned like their scalar.
+//!
+//! They have the layout of a WGSL `array<T, N>`, and the same layout in every [`LayoutMode`].
+//!
+//! ```
+//! # use serde::Serialize;
+//! # use serde_webgpu::packed::packed_vec3;
+//! # use serde_webgpu::vec::vec3;
+//! # use serde_webgpu::{serialize_webgpu_with, LayoutMode, WebGPUConfig};
+//! #[derive(Serialize)]
+//! struct Vertex {
+//!     position: packed_vec3<f32>,
+//!     normal: vec3<f32>,
+//!     weight: f32,
+//! }
+//!
+//! let vertex = Vertex {
+//!     position: packed_vec3([1.0; 3]),
+//!     normal: vec3([0.0, 1.0, 0.0]),
+//!     weight: 0.5,
+//! };
+//! let msl = WebGPUConfig {
+//!     layout: LayoutMode::Msl,
+//!     ..Default::default()
+//! };
+//! let buffer = serialize_webgpu_with(&vertex, &msl).unwrap();
+//! // `position` takes 12 bytes, while `normal` is padded to 16.
+//! assert_eq!(buffer.len(), 48);
+//! assert_eq!(buffer[32..36], 0.5f32.to_le_bytes());
+//! ```
+//!
+//! [`LayoutMode`]: crate::LayoutMode
+
+#![allow(non_camel_case_types)]
+
+use serde::ser::SerializeTupleStruct;
+use serde::{Serialize, Serializer};
+
+use crate::layout::WgslLayout;
+use crate::scalar::Scalar;
+use crate::vec::Vector;
+
+/// A vector of `N` components, with `N` from 2 to 4, without the padding of [`Vector`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct PackedVector<T, const N: usize>(pub [T; N]);
+
+pub type packed_vec2<T> = PackedVector<T, 2>;
+pub type packed_vec3<T> = PackedVector<T, 3>;
+pub type packed_vec4<T> = PackedVector<T, 4>;
+
+pub const fn packed_vec2<T>(value: [T; 2]) -> packed_vec2<T> {
+    PackedVector(value)
+}
+
+pub const fn packed_vec3<T>(value: [T; 3]) -> packed_vec3<T> {
+    PackedVector(value)
+}
+
+pub const fn packed_vec4<T>(value: [T; 4]) -> packed_vec4<T> {
+    PackedVector(value)
+}
+
+impl<T: Scalar, const N: usize> PackedVector<T, N> {
+    const NAME: &'static str = match N {
+        2..=4 => T::PACKED_VECTOR_NAMES[N - 2],
+        _ => panic!("packed vectors have 2 to 4 components"),
+    };
+}
+
+impl<T: Default, const N: usize> Default for PackedVector<T, N> {
+    fn default() -> Self {
+        Self(std::array::from_fn(|_| T::default()))
+    }
+}
+
+impl<T, const N: usize> From<Vector<T, N>> for PackedVector<T, N> {
+    fn from(value: Vector<T, N>) -> Self {
+        Self(value.0)
+    }
+}
+
+impl<T, const N: usize> From<PackedVector<T, N>> for Vector<T, N> {
+    fn from(value: PackedVector<T, N>) -> Self {
+        Self(value.0)
+    }
+}
+
+impl<T, const N: usize> From<[T; N]> for PackedVector<T, N> {
+    fn from(value: [T; N]) -> Self {
+        Self(value)
+    }
+}
+
+impl<T: Scalar, const N: usize> Serialize for PackedVector<T, N> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut s = serializer.serialize_tuple_struct(Self::NAME, N)?;
+        for i in &self.0 {
+            s.serialize_field(i)?;
+        }
+        s.end()
+    }
+}
+
+impl<T: Scalar, const N: usize> WgslLayout for PackedVector<T, N> {
+    const SIZE: usize = N * T::SIZE;
+    const ALIGN: usize = T::ALIGN;
+    const WGSL_TYPE_NAME: &'static str = "array";
+
+    fn wgsl_type() -> String {
+        format!("array<{}, {}>", T::wgsl_type(), N)
+    }
+}
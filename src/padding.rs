@@ -0,0 +1,239 @@
+//! Padding analysis of structs, with the member order that minimizes their size.
+//!
+//! The analysis works on [`WgslLayout::FIELDS`], and recurses into members that are structs.
+//! Members keep their `@align` and `@size` attributes when they are moved.
+//! Assert on [`PaddingReport::savings`] in a test to keep large uniform blocks lean.
+//!
+//! ```
+//! # use serde_webgpu::layout::{WgslField, WgslLayout};
+//! # use serde_webgpu::padding::analyze_padding;
+//! # use serde_webgpu::vec::vec3;
+//! struct Light;
+//!
+//! impl WgslLayout for Light {
+//!     const SIZE: usize = 48;
+//!     const ALIGN: usize = 16;
+//!     const WGSL_TYPE_NAME: &'static str = "Light";
+//!     const FIELDS: &'static [WgslField] = &[
+//!         WgslField::new::<f32>("intensity", 0),
+//!         WgslField::new::<vec3<f32>>("position", 16),
+//!         WgslField::new::<f32>("range", 28),
+//!         WgslField::new::<vec3<f32>>("color", 32),
+//!     ];
+//! }
+//!
+//! let report = analyze_padding::<Light>();
+//! assert_eq!((report.padding(), report.savings()), (16, 16));
+//! let order: Vec<_> = report.suggested.iter().map(|i| i.name).collect();
+//! assert_eq!(order, ["position", "intensity", "color", "range"]);
+//! println!("{}", report);
+//! // Light: 48 bytes with 16 bytes of padding
+//! // reordered: 32 bytes with 0 bytes of padding
+//! //   struct Light {
+//! // -     intensity: f32,
+//! //       position: vec3<f32>,
+//! // -     range: f32,
+//! // +     intensity: f32,
+//! //       color: vec3<f32>,
+//! // +     range: f32,
+//! //   }
+//! ```
+//!
+
+use std::fmt::{Display, Formatter};
+
+use crate::layout::{round_up, WgslField, WgslLayout};
+
+/// Members beyond which the order is found by sorting instead of an exhaustive search.
+const MAX_SEARCH_FIELDS: usize = 16;
+
+#[derive(Clone, Debug)]
+pub struct PaddingReport {
+    pub type_name: String,
+    pub size: usize,
+    pub align: usize,
+    pub fields: Vec<WgslField>,
+    /// The members in the order of the smallest size, at their new offsets.
+    pub suggested: Vec<WgslField>,
+    pub suggested_size: usize,
+    /// The reports of the members that are structs.
+    pub nested: Vec<PaddingReport>,
+}
+
+impl PaddingReport {
+    fn new(type_name: String, size: usize, align: usize, fields: &[WgslField]) -> Self {
+        let mut suggested = match fields.len() {
+            0..=MAX_SEARCH_FIELDS => search(fields),
+            _ => {
+                let mut sorted = fields.to_vec();
+                sorted.sort_by_key(|i| std::cmp::Reverse(i.member_align()));
+                sorted
+            }
+        };
+        let mut suggested_size = place(&mut suggested, align);
+        if suggested_size >= size {
+            suggested = fields.to_vec();
+            suggested_size = size;
+        }
+
+        let mut nested: Vec<PaddingReport> = Vec::new();
+        for field in fields.iter().filter(|i| !i.fields.is_empty()) {
+            let type_name = (field.wgsl_type)();
+            if nested.iter().all(|i| i.type_name != type_name) {
+                nested.push(Self::new(type_name, field.size, field.align, field.fields));
+            }
+        }
+
+        Self {
+            type_name,
+            size,
+            align,
+            fields: fields.to_vec(),
+            suggested,
+            suggested_size,
+            nested,
+        }
+    }
+
+    /// The bytes not covered by a member, including the padding at the end. The space reserved
+    /// by `@size` belongs to its member.
+    pub fn padding(&self) -> usize {
+        self.size - self.member_sizes()
+    }
+
+    pub fn suggested_padding(&self) -> usize {
+        self.suggested_size - self.member_sizes()
+    }
+
+    fn member_sizes(&self) -> usize {
+        self.fields.iter().map(WgslField::member_size).sum()
+    }
+
+    /// The bytes saved by the suggested order.
+    pub fn savings(&self) -> usize {
+        self.size - self.suggested_size
+    }
+
+    /// The bytes saved by the suggested orders of this struct and the nested ones.
+    pub fn total_savings(&self) -> usize {
+        self.savings() + self.nested.iter().map(|i| i.total_savings()).sum::<usize>()
+    }
+
+    /// A line diff from the WGSL declaration of the struct to the suggested one.
+    pub fn diff(&self) -> String {
+        let before = declaration(&self.type_name, &self.fields);
+        let after = declaration(&self.type_name, &self.suggested);
+
+        // Longest common subsequence, walked from the front.
+        let mut common = vec![vec![0; after.len() + 1]; before.len() + 1];
+        for i in (0..before.len()).rev() {
+            for j in (0..after.len()).rev() {
+                common[i][j] = match before[i] == after[j] {
+                    true => common[i + 1][j + 1] + 1,
+                    false => common[i + 1][j].max(common[i][j + 1]),
+                };
+            }
+        }
+        let mut diff = String::new();
+        let (mut i, mut j) = (0, 0);
+        while i < before.len() || j < after.len() {
+            if i < before.len() && j < after.len() && before[i] == after[j] {
+                diff += &format!("  {}\n", before[i]);
+                i += 1;
+                j += 1;
+            } else if j == after.len() || (i < before.len() && common[i + 1][j] >= common[i][j + 1])
+            {
+                diff += &format!("- {}\n", before[i]);
+                i += 1;
+            } else {
+                diff += &format!("+ {}\n", after[j]);
+                j += 1;
+            }
+        }
+        diff
+    }
+}
+
+impl Display for PaddingReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "{}: {} bytes with {} bytes of padding",
+            self.type_name,
+            self.size,
+            self.padding()
+        )?;
+        if self.savings() > 0 {
+            writeln!(
+                f,
+                "reordered: {} bytes with {} bytes of padding",
+                self.suggested_size,
+                self.suggested_padding()
+            )?;
+            write!(f, "{}", self.diff())?;
+        }
+        for nested in &self.nested {
+            writeln!(f)?;
+            write!(f, "{}", nested)?;
+        }
+        Ok(())
+    }
+}
+
+pub fn analyze_padding<T: WgslLayout>() -> PaddingReport {
+    PaddingReport::new(T::wgsl_type(), T::SIZE, T::ALIGN, T::FIELDS)
+}
+
+/// Lay out the members in order, returning the struct size.
+fn place(fields: &mut [WgslField], align: usize) -> usize {
+    let mut end = 0;
+    for field in fields {
+        field.offset = round_up(field.member_align(), end);
+        end = field.offset + field.member_size();
+    }
+    round_up(align, end)
+}
+
+/// The order with the smallest end offset, found over all subsets of members. The smallest end
+/// of a subset is the best start for the remaining members, since alignment only rounds up.
+fn search(fields: &[WgslField]) -> Vec<WgslField> {
+    let n = fields.len();
+    let mut best = vec![(usize::MAX, 0, 0); 1 << n];
+    best[0] = (0, 0, 0);
+    for mask in 0..(1usize << n) {
+        let (end, _, _) = best[mask];
+        for (i, field) in fields
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| mask & 1 << i == 0)
+        {
+            let next = round_up(field.member_align(), end) + field.member_size();
+            if next < best[mask | 1 << i].0 {
+                best[mask | 1 << i] = (next, mask, i);
+            }
+        }
+    }
+    let mut order = Vec::with_capacity(n);
+    let mut mask = (1 << n) - 1;
+    while mask != 0 {
+        let (_, previous, i) = best[mask];
+        order.push(fields[i]);
+        mask = previous;
+    }
+    order.reverse();
+    order
+}
+
+fn declaration(type_name: &str, fields: &[WgslField]) -> Vec<String> {
+    let mut lines = vec![format!("struct {} {{", type_name)];
+    for field in fields {
+        lines.push(format!(
+            "    {}{}: {},",
+            field.attributes(),
+            field.name,
+            (field.wgsl_type)()
+        ));
+    }
+    lines.push("}".to_string());
+    lines
+}
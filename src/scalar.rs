@@ -0,0 +1,136 @@
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+use serde::Serialize;
+
+use crate::f16;
+use crate::layout::WgslLayout;
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// A WGSL scalar, the component type of vectors and matrices.
+///
+/// The size and alignment are those of [`WgslLayout`]. The trait is sealed, since the serializer
+/// lays out vectors by the name of their scalar.
+pub trait Scalar: Copy + Serialize + WgslLayout + sealed::Sealed {
+    /// The WGSL type name, such as `f32`.
+    const WGSL_NAME: &'static str;
+    /// The name tags of `vec2`, `vec3` and `vec4`, such as `vec3@f32`.
+    const VECTOR_NAMES: [&'static str; 3];
+    /// The WGSL types of `vec2`, `vec3` and `vec4` in a host-shareable layout, such as
+    /// `vec3<f32>`.
+    const VECTOR_TYPE_NAMES: [&'static str; 3];
+    /// The name tags of packed vectors, such as `packed_vec3@f32`.
+    const PACKED_VECTOR_NAMES: [&'static str; 3];
+}
+
+macro_rules! scalar {
+    ($($t:ident as $wgsl:ident: $size:literal),* $(,)?) => {
+        $(
+            impl sealed::Sealed for $t {}
+
+            impl Scalar for $t {
+                const WGSL_NAME: &'static str = stringify!($t);
+                const VECTOR_NAMES: [&'static str; 3] = [
+                    concat!("vec2@", stringify!($t)),
+                    concat!("vec3@", stringify!($t)),
+                    concat!("vec4@", stringify!($t)),
+                ];
+                const VECTOR_TYPE_NAMES: [&'static str; 3] = [
+                    concat!("vec2<", stringify!($wgsl), ">"),
+                    concat!("vec3<", stringify!($wgsl), ">"),
+                    concat!("vec4<", stringify!($wgsl), ">"),
+                ];
+                const PACKED_VECTOR_NAMES: [&'static str; 3] = [
+                    concat!("packed_vec2@", stringify!($t)),
+                    concat!("packed_vec3@", stringify!($t)),
+                    concat!("packed_vec4@", stringify!($t)),
+                ];
+            }
+
+            impl WgslLayout for $t {
+                const SIZE: usize = $size;
+                const ALIGN: usize = $size;
+                const WGSL_TYPE_NAME: &'static str = stringify!($wgsl);
+            }
+        )*
+
+        /// The size and alignment of a scalar by its name in a vector name tag, such as `f32` in
+        /// `vec3@f32`.
+        pub(crate) fn scalar_layout(name: &str) -> Option<(usize, usize)> {
+            match name {
+                $(stringify!($t) => Some((<$t as WgslLayout>::SIZE, <$t as WgslLayout>::ALIGN)),)*
+                _ => None,
+            }
+        }
+    };
+}
+
+// `bool` is not host-shareable, so it is laid out as the `u32` it is widened to.
+scalar!(
+    bool as u32: 4,
+    f16 as f16: 2,
+    i32 as i32: 4,
+    u32 as u32: 4,
+    f32 as f32: 4,
+    i64 as i64: 8,
+    u64 as u64: 8,
+    f64 as f64: 8,
+);
+
+/// Floating point scalars, for the math of vectors and matrices.
+pub trait Float:
+    Scalar
+    + PartialOrd
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Neg<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn abs(self) -> Self;
+    fn sqrt(self) -> Self;
+}
+
+impl Float for f16 {
+    const ZERO: Self = f16::ZERO;
+    const ONE: Self = f16::ONE;
+
+    fn abs(self) -> Self {
+        f16::from_bits(self.to_bits() & 0x7FFF)
+    }
+
+    fn sqrt(self) -> Self {
+        f16::from_f32(self.to_f32().sqrt())
+    }
+}
+
+impl Float for f32 {
+    const ZERO: Self = 0.0;
+    const ONE: Self = 1.0;
+
+    fn abs(self) -> Self {
+        f32::abs(self)
+    }
+
+    fn sqrt(self) -> Self {
+        f32::sqrt(self)
+    }
+}
+
+impl Float for f64 {
+    const ZERO: Self = 0.0;
+    const ONE: Self = 1.0;
+
+    fn abs(self) -> Self {
+        f64::abs(self)
+    }
+
+    fn sqrt(self) -> Self {
+        f64::sqrt(self)
+    }
+}
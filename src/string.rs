@@ -0,0 +1,152 @@
+//! Strings of a fixed capacity, stored as a length and an `array<vec4<u32>>` of packed bytes.
+//!
+//! Byte `i` is stored in the bits `8 * (i % 4)..` of the word `i / 4`, matching `unpack4xU8`.
+//! The words are grouped by four into `data[i / 16]`, so that the array stride is 16 bytes as
+//! the uniform address space, `std140` and HLSL constant buffers require.
+//!
+//! ```
+//! # use serde_webgpu::serialize_webgpu;
+//! # use serde_webgpu::string::WgslString;
+//! let label = WgslString::<8>::new("hello").unwrap();
+//! let buffer = serialize_webgpu(&label).unwrap();
+//! assert_eq!(buffer.len(), 32);
+//! assert_eq!(buffer[..4], [5, 0, 0, 0]);
+//! assert_eq!(buffer[16..24], [b'h', b'e', b'l', b'l', b'o', 0, 0, 0]);
+//! assert!(WgslString::<4>::new("hello").is_err());
+//! ```
+//!
+
+use std::fmt::{Debug, Display, Formatter};
+use std::ops::Deref;
+
+use serde::ser::{SerializeTuple, SerializeTupleStruct};
+use serde::{Serialize, Serializer};
+
+use crate::layout::WgslLayout;
+use crate::vec::vec4;
+use crate::{ErrorKind, WebGPUSerializeError};
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash)]
+pub struct WgslString<const N: usize> {
+    len: usize,
+    bytes: [u8; N],
+}
+
+impl<const N: usize> WgslString<N> {
+    /// The number of `vec4<u32>`, each holding 16 bytes.
+    const CHUNKS: usize = {
+        assert!(N > 0, "WgslString needs a capacity");
+        N.div_ceil(16)
+    };
+
+    pub fn new(s: &str) -> Result<Self, WebGPUSerializeError> {
+        if s.len() > N {
+            return Err(WebGPUSerializeError::new(
+                ErrorKind::Capacity,
+                format!("string of {} bytes exceeds the capacity {}", s.len(), N),
+            ));
+        }
+        Ok(Self::truncate(s))
+    }
+
+    /// Keep as many characters as fit into the capacity.
+    pub fn truncate(s: &str) -> Self {
+        let mut len = s.len().min(N);
+        while !s.is_char_boundary(len) {
+            len -= 1;
+        }
+        let mut bytes = [0; N];
+        bytes[..len].copy_from_slice(&s.as_bytes()[..len]);
+        Self { len, bytes }
+    }
+
+    pub fn as_str(&self) -> &str {
+        std::str::from_utf8(&self.bytes[..self.len]).unwrap()
+    }
+
+    /// The WGSL struct of this capacity, and a function reading byte `i` from
+    /// `data[i / 16u]`, as in `wgsl_string_byte(label.data[i / 16u], i)`.
+    pub fn wgsl_decl() -> String {
+        format!(
+            "struct WgslString{n} {{\n    len: u32,\n    data: array<vec4<u32>, {chunks}>,\n}}\n\n\
+             fn wgsl_string_byte(chunk: vec4<u32>, i: u32) -> u32 {{\n    \
+             return (chunk[(i / 4u) % 4u] >> ((i % 4u) * 8u)) & 0xFFu;\n}}\n",
+            n = N,
+            chunks = Self::CHUNKS,
+        )
+    }
+}
+
+impl<const N: usize> Default for WgslString<N> {
+    fn default() -> Self {
+        Self {
+            len: 0,
+            bytes: [0; N],
+        }
+    }
+}
+
+impl<const N: usize> Deref for WgslString<N> {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        self.as_str()
+    }
+}
+
+impl<const N: usize> Debug for WgslString<N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl<const N: usize> Display for WgslString<N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(self.as_str(), f)
+    }
+}
+
+impl<const N: usize> WgslLayout for WgslString<N> {
+    const SIZE: usize = 16 + 16 * Self::CHUNKS;
+    const ALIGN: usize = 16;
+    const WGSL_TYPE_NAME: &'static str = "WgslString";
+
+    fn wgsl_type() -> String {
+        format!("WgslString{}", N)
+    }
+}
+
+impl<const N: usize> Serialize for WgslString<N> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut s = serializer.serialize_tuple_struct("WgslString", 2)?;
+        s.serialize_field(&(self.len as u32))?;
+        s.serialize_field(&Chunks(self))?;
+        s.end()
+    }
+}
+
+/// The bytes of a [`WgslString`] as an `array<vec4<u32>>`.
+struct Chunks<'a, const N: usize>(&'a WgslString<N>);
+
+impl<const N: usize> Serialize for Chunks<'_, N> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut s = serializer.serialize_tuple(WgslString::<N>::CHUNKS)?;
+        for i in 0..WgslString::<N>::CHUNKS {
+            let chunk: [u32; 4] = std::array::from_fn(|j| {
+                let mut word = [0; 4];
+                for (k, byte) in word.iter_mut().enumerate() {
+                    *byte = self.0.bytes.get(i * 16 + j * 4 + k).copied().unwrap_or(0);
+                }
+                u32::from_le_bytes(word)
+            });
+            s.serialize_element(&vec4(chunk))?;
+        }
+        s.end()
+    }
+}
@@ -0,0 +1,111 @@
+//! Enums with fields are serialized as a tagged union.
+//!
+//! The union is an `u32` tag followed by a payload sized and aligned for the largest variant, so
+//! every variant has the same layout and unused payload bytes are zero. The layout is measured
+//! from one sample of each variant, given by [`WgslUnion::variants`]:
+//!
+//! ```wgsl
+//! struct Sdf {
+//!     tag: u32,
+//!     payload: vec4<u32>, // or any type of the largest size and alignment
+//! }
+//! ```
+//!
+//! The tag is the variant index. Serde does not pass it for `#[serde(tag = "...")]` enums, so
+//! their tag is the position of the variant name in [`WgslUnion::VARIANTS`]. Adjacently tagged
+//! enums are rejected.
+//!
+//! Other serializers only see the value, so the same type serializes as usual to JSON.
+//!
+//! ```
+//! # use serde::Serialize;
+//! # use serde_webgpu::serialize_webgpu;
+//! # use serde_webgpu::tagged::{Tagged, WgslUnion};
+//! # use serde_webgpu::vec::vec3;
+//! #[derive(Serialize)]
+//! enum Sdf {
+//!     Sphere { radius: f32 },
+//!     Box(vec3<f32>),
+//!     Plane,
+//! }
+//!
+//! impl WgslUnion for Sdf {
+//!     fn variants() -> Vec<Self> {
+//!         vec![Sdf::Sphere { radius: 0.0 }, Sdf::Box(vec3([0.0; 3])), Sdf::Plane]
+//!     }
+//! }
+//!
+//! let buffer = serialize_webgpu(&Tagged(Sdf::Sphere { radius: 2.0 })).unwrap();
+//! assert_eq!(buffer.len(), 32);
+//! assert_eq!(buffer[16..20], 2.0f32.to_le_bytes());
+//!
+//! let buffer = serialize_webgpu(&Tagged(Sdf::Plane)).unwrap();
+//! assert_eq!(buffer[..4], [2, 0, 0, 0]);
+//! ```
+//!
+
+use std::cell::Cell;
+
+use serde::ser::SerializeTupleStruct;
+use serde::{Serialize, Serializer};
+
+thread_local! {
+    /// Set by the WebGPU serializer right before it serializes a [`Union`], which then passes
+    /// the samples along with the value.
+    pub(crate) static WEBGPU_UNION: Cell<bool> = const { Cell::new(false) };
+}
+
+/// An enum serialized as a tagged union.
+pub trait WgslUnion: Serialize + Sized {
+    /// The variant names in declaration order, required for internally tagged enums.
+    ///
+    /// When given, they are checked against the variant indices of externally tagged samples.
+    const VARIANTS: &'static [&'static str] = &[];
+
+    /// A sample of each variant, in any order.
+    fn variants() -> Vec<Self>;
+}
+
+/// Serialize a [`WgslUnion`] as a tagged union, for `#[serde(serialize_with = "...")]`.
+pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: WgslUnion,
+    S: Serializer,
+{
+    serializer.serialize_newtype_struct("union", &Union(value))
+}
+
+/// A value serialized with the names and samples of its union for the WebGPU serializer, and
+/// as itself for any other.
+struct Union<'a, T>(&'a T);
+
+impl<T: WgslUnion> Serialize for Union<'_, T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if !WEBGPU_UNION.with(|i| i.replace(false)) {
+            return self.0.serialize(serializer);
+        }
+        let variants = T::variants();
+        let mut s = serializer.serialize_tuple_struct("union", variants.len() + 2)?;
+        s.serialize_field(T::VARIANTS)?;
+        for i in &variants {
+            s.serialize_field(i)?;
+        }
+        s.serialize_field(self.0)?;
+        s.end()
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct Tagged<T>(pub T);
+
+impl<T: WgslUnion> Serialize for Tagged<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize(&self.0, serializer)
+    }
+}
@@ -0,0 +1,675 @@
+//! Encode pixels into WebGPU texel formats and decode them back.
+//!
+//! Pixels are given as `vec4<f32>` in `rgba` order. Missing channels decode as `(0, 0, 0, 1)`,
+//! like a texture sample, and depth or stencil values are read from the first channel.
+//!
+//! ```
+//! # use serde_webgpu::texture::{decode_texels, encode_texels, TextureFormat};
+//! # use serde_webgpu::vec::vec4;
+//! let pixels = [vec4([1.0, 0.5, 0.0, 1.0])];
+//!
+//! let bytes = encode_texels(TextureFormat::Bgra8Unorm, &pixels).unwrap();
+//! assert_eq!(bytes, [0, 128, 255, 255]);
+//!
+//! let decoded = decode_texels(TextureFormat::Bgra8Unorm, &bytes).unwrap();
+//! assert_eq!(decoded[0].0, [1.0, 128.0 / 255.0, 0.0, 1.0]);
+//! ```
+//!
+
+use crate::f16;
+use crate::vec::vec4;
+use crate::{ErrorKind, WebGPUSerializeError};
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum TextureFormat {
+    R8Unorm,
+    R8Snorm,
+    R8Uint,
+    R8Sint,
+    R16Uint,
+    R16Sint,
+    R16Float,
+    Rg8Unorm,
+    Rg8Snorm,
+    Rg8Uint,
+    Rg8Sint,
+    R32Uint,
+    R32Sint,
+    R32Float,
+    Rg16Uint,
+    Rg16Sint,
+    Rg16Float,
+    Rgba8Unorm,
+    Rgba8UnormSrgb,
+    Rgba8Snorm,
+    Rgba8Uint,
+    Rgba8Sint,
+    Bgra8Unorm,
+    Bgra8UnormSrgb,
+    Rgb9e5Ufloat,
+    Rgb10a2Uint,
+    Rgb10a2Unorm,
+    Rg11b10Ufloat,
+    Rg32Uint,
+    Rg32Sint,
+    Rg32Float,
+    Rgba16Uint,
+    Rgba16Sint,
+    Rgba16Float,
+    Rgba32Uint,
+    Rgba32Sint,
+    Rgba32Float,
+    Stencil8,
+    Depth16Unorm,
+    Depth32Float,
+    Bc1RgbaUnorm,
+    Bc1RgbaUnormSrgb,
+    Bc2RgbaUnorm,
+    Bc2RgbaUnormSrgb,
+    Bc3RgbaUnorm,
+    Bc3RgbaUnormSrgb,
+    Bc4RUnorm,
+    Bc4RSnorm,
+    Bc5RgUnorm,
+    Bc5RgSnorm,
+    Bc6hRgbUfloat,
+    Bc6hRgbFloat,
+    Bc7RgbaUnorm,
+    Bc7RgbaUnormSrgb,
+    Etc2Rgb8Unorm,
+    Etc2Rgb8UnormSrgb,
+    Etc2Rgb8a1Unorm,
+    Etc2Rgb8a1UnormSrgb,
+    Etc2Rgba8Unorm,
+    Etc2Rgba8UnormSrgb,
+    EacR11Unorm,
+    EacR11Snorm,
+    EacRg11Unorm,
+    EacRg11Snorm,
+    Astc4x4Unorm,
+    Astc4x4UnormSrgb,
+    Astc5x4Unorm,
+    Astc5x4UnormSrgb,
+    Astc5x5Unorm,
+    Astc5x5UnormSrgb,
+    Astc6x5Unorm,
+    Astc6x5UnormSrgb,
+    Astc6x6Unorm,
+    Astc6x6UnormSrgb,
+    Astc8x5Unorm,
+    Astc8x5UnormSrgb,
+    Astc8x6Unorm,
+    Astc8x6UnormSrgb,
+    Astc8x8Unorm,
+    Astc8x8UnormSrgb,
+    Astc10x5Unorm,
+    Astc10x5UnormSrgb,
+    Astc10x6Unorm,
+    Astc10x6UnormSrgb,
+    Astc10x8Unorm,
+    Astc10x8UnormSrgb,
+    Astc10x10Unorm,
+    Astc10x10UnormSrgb,
+    Astc12x10Unorm,
+    Astc12x10UnormSrgb,
+    Astc12x12Unorm,
+    Astc12x12UnormSrgb,
+}
+
+#[derive(Copy, Clone, Debug)]
+enum Channel {
+    Unorm8,
+    Snorm8,
+    Uint8,
+    Sint8,
+    Unorm16,
+    Uint16,
+    Sint16,
+    Float16,
+    Uint32,
+    Sint32,
+    Float32,
+}
+
+impl Channel {
+    fn size(self) -> usize {
+        match self {
+            Channel::Unorm8 | Channel::Snorm8 | Channel::Uint8 | Channel::Sint8 => 1,
+            Channel::Unorm16 | Channel::Uint16 | Channel::Sint16 | Channel::Float16 => 2,
+            Channel::Uint32 | Channel::Sint32 | Channel::Float32 => 4,
+        }
+    }
+
+    fn encode(self, v: f32, buffer: &mut Vec<u8>) {
+        match self {
+            Channel::Unorm8 => buffer.push(unorm(v, 8) as u8),
+            Channel::Snorm8 => buffer.push((v.clamp(-1.0, 1.0) * 127.0).round() as i8 as u8),
+            Channel::Uint8 => buffer.push(v.round().clamp(0.0, u8::MAX as f32) as u8),
+            Channel::Sint8 => {
+                buffer.push(v.round().clamp(i8::MIN as f32, i8::MAX as f32) as i8 as u8)
+            }
+            Channel::Unorm16 => buffer.extend_from_slice(&(unorm(v, 16) as u16).to_le_bytes()),
+            Channel::Uint16 => {
+                let v = v.round().clamp(0.0, u16::MAX as f32) as u16;
+                buffer.extend_from_slice(&v.to_le_bytes());
+            }
+            Channel::Sint16 => {
+                let v = v.round().clamp(i16::MIN as f32, i16::MAX as f32) as i16;
+                buffer.extend_from_slice(&v.to_le_bytes());
+            }
+            Channel::Float16 => buffer.extend_from_slice(&f16::from_f32(v).to_le_bytes()),
+            // `as` saturates, which is the clamping we want for 32 bit integers.
+            Channel::Uint32 => buffer.extend_from_slice(&(v.round() as u32).to_le_bytes()),
+            Channel::Sint32 => buffer.extend_from_slice(&(v.round() as i32).to_le_bytes()),
+            Channel::Float32 => buffer.extend_from_slice(&v.to_le_bytes()),
+        }
+    }
+
+    fn decode(self, bytes: &[u8]) -> f32 {
+        match self {
+            Channel::Unorm8 => bytes[0] as f32 / 255.0,
+            Channel::Snorm8 => (bytes[0] as i8 as f32 / 127.0).max(-1.0),
+            Channel::Uint8 => bytes[0] as f32,
+            Channel::Sint8 => bytes[0] as i8 as f32,
+            Channel::Unorm16 => u16::from_le_bytes([bytes[0], bytes[1]]) as f32 / 65535.0,
+            Channel::Uint16 => u16::from_le_bytes([bytes[0], bytes[1]]) as f32,
+            Channel::Sint16 => i16::from_le_bytes([bytes[0], bytes[1]]) as f32,
+            Channel::Float16 => f16::from_le_bytes([bytes[0], bytes[1]]).to_f32(),
+            Channel::Uint32 => u32::from_le_bytes(bytes[..4].try_into().unwrap()) as f32,
+            Channel::Sint32 => i32::from_le_bytes(bytes[..4].try_into().unwrap()) as f32,
+            Channel::Float32 => f32::from_le_bytes(bytes[..4].try_into().unwrap()),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+enum TexelLayout {
+    Channels(Channel, usize),
+    Srgb,
+    Bgra,
+    BgraSrgb,
+    Rgb9e5Ufloat,
+    Rgb10a2Uint,
+    Rgb10a2Unorm,
+    Rg11b10Ufloat,
+    Compressed {
+        size: usize,
+        width: u32,
+        height: u32,
+    },
+}
+
+/// A block compressed layout of `size` bytes per `width`x`height` block.
+const fn compressed(size: usize, width: u32, height: u32) -> TexelLayout {
+    TexelLayout::Compressed {
+        size,
+        width,
+        height,
+    }
+}
+
+impl TextureFormat {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            TextureFormat::R8Unorm => "r8unorm",
+            TextureFormat::R8Snorm => "r8snorm",
+            TextureFormat::R8Uint => "r8uint",
+            TextureFormat::R8Sint => "r8sint",
+            TextureFormat::R16Uint => "r16uint",
+            TextureFormat::R16Sint => "r16sint",
+            TextureFormat::R16Float => "r16float",
+            TextureFormat::Rg8Unorm => "rg8unorm",
+            TextureFormat::Rg8Snorm => "rg8snorm",
+            TextureFormat::Rg8Uint => "rg8uint",
+            TextureFormat::Rg8Sint => "rg8sint",
+            TextureFormat::R32Uint => "r32uint",
+            TextureFormat::R32Sint => "r32sint",
+            TextureFormat::R32Float => "r32float",
+            TextureFormat::Rg16Uint => "rg16uint",
+            TextureFormat::Rg16Sint => "rg16sint",
+            TextureFormat::Rg16Float => "rg16float",
+            TextureFormat::Rgba8Unorm => "rgba8unorm",
+            TextureFormat::Rgba8UnormSrgb => "rgba8unorm-srgb",
+            TextureFormat::Rgba8Snorm => "rgba8snorm",
+            TextureFormat::Rgba8Uint => "rgba8uint",
+            TextureFormat::Rgba8Sint => "rgba8sint",
+            TextureFormat::Bgra8Unorm => "bgra8unorm",
+            TextureFormat::Bgra8UnormSrgb => "bgra8unorm-srgb",
+            TextureFormat::Rgb9e5Ufloat => "rgb9e5ufloat",
+            TextureFormat::Rgb10a2Uint => "rgb10a2uint",
+            TextureFormat::Rgb10a2Unorm => "rgb10a2unorm",
+            TextureFormat::Rg11b10Ufloat => "rg11b10ufloat",
+            TextureFormat::Rg32Uint => "rg32uint",
+            TextureFormat::Rg32Sint => "rg32sint",
+            TextureFormat::Rg32Float => "rg32float",
+            TextureFormat::Rgba16Uint => "rgba16uint",
+            TextureFormat::Rgba16Sint => "rgba16sint",
+            TextureFormat::Rgba16Float => "rgba16float",
+            TextureFormat::Rgba32Uint => "rgba32uint",
+            TextureFormat::Rgba32Sint => "rgba32sint",
+            TextureFormat::Rgba32Float => "rgba32float",
+            TextureFormat::Stencil8 => "stencil8",
+            TextureFormat::Depth16Unorm => "depth16unorm",
+            TextureFormat::Depth32Float => "depth32float",
+            TextureFormat::Bc1RgbaUnorm => "bc1-rgba-unorm",
+            TextureFormat::Bc1RgbaUnormSrgb => "bc1-rgba-unorm-srgb",
+            TextureFormat::Bc2RgbaUnorm => "bc2-rgba-unorm",
+            TextureFormat::Bc2RgbaUnormSrgb => "bc2-rgba-unorm-srgb",
+            TextureFormat::Bc3RgbaUnorm => "bc3-rgba-unorm",
+            TextureFormat::Bc3RgbaUnormSrgb => "bc3-rgba-unorm-srgb",
+            TextureFormat::Bc4RUnorm => "bc4-r-unorm",
+            TextureFormat::Bc4RSnorm => "bc4-r-snorm",
+            TextureFormat::Bc5RgUnorm => "bc5-rg-unorm",
+            TextureFormat::Bc5RgSnorm => "bc5-rg-snorm",
+            TextureFormat::Bc6hRgbUfloat => "bc6h-rgb-ufloat",
+            TextureFormat::Bc6hRgbFloat => "bc6h-rgb-float",
+            TextureFormat::Bc7RgbaUnorm => "bc7-rgba-unorm",
+            TextureFormat::Bc7RgbaUnormSrgb => "bc7-rgba-unorm-srgb",
+            TextureFormat::Etc2Rgb8Unorm => "etc2-rgb8unorm",
+            TextureFormat::Etc2Rgb8UnormSrgb => "etc2-rgb8unorm-srgb",
+            TextureFormat::Etc2Rgb8a1Unorm => "etc2-rgb8a1unorm",
+            TextureFormat::Etc2Rgb8a1UnormSrgb => "etc2-rgb8a1unorm-srgb",
+            TextureFormat::Etc2Rgba8Unorm => "etc2-rgba8unorm",
+            TextureFormat::Etc2Rgba8UnormSrgb => "etc2-rgba8unorm-srgb",
+            TextureFormat::EacR11Unorm => "eac-r11unorm",
+            TextureFormat::EacR11Snorm => "eac-r11snorm",
+            TextureFormat::EacRg11Unorm => "eac-rg11unorm",
+            TextureFormat::EacRg11Snorm => "eac-rg11snorm",
+            TextureFormat::Astc4x4Unorm => "astc-4x4-unorm",
+            TextureFormat::Astc4x4UnormSrgb => "astc-4x4-unorm-srgb",
+            TextureFormat::Astc5x4Unorm => "astc-5x4-unorm",
+            TextureFormat::Astc5x4UnormSrgb => "astc-5x4-unorm-srgb",
+            TextureFormat::Astc5x5Unorm => "astc-5x5-unorm",
+            TextureFormat::Astc5x5UnormSrgb => "astc-5x5-unorm-srgb",
+            TextureFormat::Astc6x5Unorm => "astc-6x5-unorm",
+            TextureFormat::Astc6x5UnormSrgb => "astc-6x5-unorm-srgb",
+            TextureFormat::Astc6x6Unorm => "astc-6x6-unorm",
+            TextureFormat::Astc6x6UnormSrgb => "astc-6x6-unorm-srgb",
+            TextureFormat::Astc8x5Unorm => "astc-8x5-unorm",
+            TextureFormat::Astc8x5UnormSrgb => "astc-8x5-unorm-srgb",
+            TextureFormat::Astc8x6Unorm => "astc-8x6-unorm",
+            TextureFormat::Astc8x6UnormSrgb => "astc-8x6-unorm-srgb",
+            TextureFormat::Astc8x8Unorm => "astc-8x8-unorm",
+            TextureFormat::Astc8x8UnormSrgb => "astc-8x8-unorm-srgb",
+            TextureFormat::Astc10x5Unorm => "astc-10x5-unorm",
+            TextureFormat::Astc10x5UnormSrgb => "astc-10x5-unorm-srgb",
+            TextureFormat::Astc10x6Unorm => "astc-10x6-unorm",
+            TextureFormat::Astc10x6UnormSrgb => "astc-10x6-unorm-srgb",
+            TextureFormat::Astc10x8Unorm => "astc-10x8-unorm",
+            TextureFormat::Astc10x8UnormSrgb => "astc-10x8-unorm-srgb",
+            TextureFormat::Astc10x10Unorm => "astc-10x10-unorm",
+            TextureFormat::Astc10x10UnormSrgb => "astc-10x10-unorm-srgb",
+            TextureFormat::Astc12x10Unorm => "astc-12x10-unorm",
+            TextureFormat::Astc12x10UnormSrgb => "astc-12x10-unorm-srgb",
+            TextureFormat::Astc12x12Unorm => "astc-12x12-unorm",
+            TextureFormat::Astc12x12UnormSrgb => "astc-12x12-unorm-srgb",
+        }
+    }
+
+    fn layout(self) -> TexelLayout {
+        use Channel::*;
+        use TexelLayout::Channels;
+        match self {
+            TextureFormat::R8Unorm => Channels(Unorm8, 1),
+            TextureFormat::R8Snorm => Channels(Snorm8, 1),
+            TextureFormat::R8Uint => Channels(Uint8, 1),
+            TextureFormat::R8Sint => Channels(Sint8, 1),
+            TextureFormat::R16Uint => Channels(Uint16, 1),
+            TextureFormat::R16Sint => Channels(Sint16, 1),
+            TextureFormat::R16Float => Channels(Float16, 1),
+            TextureFormat::Rg8Unorm => Channels(Unorm8, 2),
+            TextureFormat::Rg8Snorm => Channels(Snorm8, 2),
+            TextureFormat::Rg8Uint => Channels(Uint8, 2),
+            TextureFormat::Rg8Sint => Channels(Sint8, 2),
+            TextureFormat::R32Uint => Channels(Uint32, 1),
+            TextureFormat::R32Sint => Channels(Sint32, 1),
+            TextureFormat::R32Float => Channels(Float32, 1),
+            TextureFormat::Rg16Uint => Channels(Uint16, 2),
+            TextureFormat::Rg16Sint => Channels(Sint16, 2),
+            TextureFormat::Rg16Float => Channels(Float16, 2),
+            TextureFormat::Rgba8Unorm => Channels(Unorm8, 4),
+            TextureFormat::Rgba8UnormSrgb => TexelLayout::Srgb,
+            TextureFormat::Rgba8Snorm => Channels(Snorm8, 4),
+            TextureFormat::Rgba8Uint => Channels(Uint8, 4),
+            TextureFormat::Rgba8Sint => Channels(Sint8, 4),
+            TextureFormat::Bgra8Unorm => TexelLayout::Bgra,
+            TextureFormat::Bgra8UnormSrgb => TexelLayout::BgraSrgb,
+            TextureFormat::Rgb9e5Ufloat => TexelLayout::Rgb9e5Ufloat,
+            TextureFormat::Rgb10a2Uint => TexelLayout::Rgb10a2Uint,
+            TextureFormat::Rgb10a2Unorm => TexelLayout::Rgb10a2Unorm,
+            TextureFormat::Rg11b10Ufloat => TexelLayout::Rg11b10Ufloat,
+            TextureFormat::Rg32Uint => Channels(Uint32, 2),
+            TextureFormat::Rg32Sint => Channels(Sint32, 2),
+            TextureFormat::Rg32Float => Channels(Float32, 2),
+            TextureFormat::Rgba16Uint => Channels(Uint16, 4),
+            TextureFormat::Rgba16Sint => Channels(Sint16, 4),
+            TextureFormat::Rgba16Float => Channels(Float16, 4),
+            TextureFormat::Rgba32Uint => Channels(Uint32, 4),
+            TextureFormat::Rgba32Sint => Channels(Sint32, 4),
+            TextureFormat::Rgba32Float => Channels(Float32, 4),
+            TextureFormat::Stencil8 => Channels(Uint8, 1),
+            TextureFormat::Depth16Unorm => Channels(Unorm16, 1),
+            TextureFormat::Depth32Float => Channels(Float32, 1),
+            TextureFormat::Bc1RgbaUnorm
+            | TextureFormat::Bc1RgbaUnormSrgb
+            | TextureFormat::Bc4RUnorm
+            | TextureFormat::Bc4RSnorm
+            | TextureFormat::Etc2Rgb8Unorm
+            | TextureFormat::Etc2Rgb8UnormSrgb
+            | TextureFormat::Etc2Rgb8a1Unorm
+            | TextureFormat::Etc2Rgb8a1UnormSrgb
+            | TextureFormat::EacR11Unorm
+            | TextureFormat::EacR11Snorm => compressed(8, 4, 4),
+            TextureFormat::Bc2RgbaUnorm
+            | TextureFormat::Bc2RgbaUnormSrgb
+            | TextureFormat::Bc3RgbaUnorm
+            | TextureFormat::Bc3RgbaUnormSrgb
+            | TextureFormat::Bc5RgUnorm
+            | TextureFormat::Bc5RgSnorm
+            | TextureFormat::Bc6hRgbUfloat
+            | TextureFormat::Bc6hRgbFloat
+            | TextureFormat::Bc7RgbaUnorm
+            | TextureFormat::Bc7RgbaUnormSrgb
+            | TextureFormat::Etc2Rgba8Unorm
+            | TextureFormat::Etc2Rgba8UnormSrgb
+            | TextureFormat::EacRg11Unorm
+            | TextureFormat::EacRg11Snorm
+            | TextureFormat::Astc4x4Unorm
+            | TextureFormat::Astc4x4UnormSrgb => compressed(16, 4, 4),
+            TextureFormat::Astc5x4Unorm | TextureFormat::Astc5x4UnormSrgb => compressed(16, 5, 4),
+            TextureFormat::Astc5x5Unorm | TextureFormat::Astc5x5UnormSrgb => compressed(16, 5, 5),
+            TextureFormat::Astc6x5Unorm | TextureFormat::Astc6x5UnormSrgb => compressed(16, 6, 5),
+            TextureFormat::Astc6x6Unorm | TextureFormat::Astc6x6UnormSrgb => compressed(16, 6, 6),
+            TextureFormat::Astc8x5Unorm | TextureFormat::Astc8x5UnormSrgb => compressed(16, 8, 5),
+            TextureFormat::Astc8x6Unorm | TextureFormat::Astc8x6UnormSrgb => compressed(16, 8, 6),
+            TextureFormat::Astc8x8Unorm | TextureFormat::Astc8x8UnormSrgb => compressed(16, 8, 8),
+            TextureFormat::Astc10x5Unorm | TextureFormat::Astc10x5UnormSrgb => {
+                compressed(16, 10, 5)
+            }
+            TextureFormat::Astc10x6Unorm | TextureFormat::Astc10x6UnormSrgb => {
+                compressed(16, 10, 6)
+            }
+            TextureFormat::Astc10x8Unorm | TextureFormat::Astc10x8UnormSrgb => {
+                compressed(16, 10, 8)
+            }
+            TextureFormat::Astc10x10Unorm | TextureFormat::Astc10x10UnormSrgb => {
+                compressed(16, 10, 10)
+            }
+            TextureFormat::Astc12x10Unorm | TextureFormat::Astc12x10UnormSrgb => {
+                compressed(16, 12, 10)
+            }
+            TextureFormat::Astc12x12Unorm | TextureFormat::Astc12x12UnormSrgb => {
+                compressed(16, 12, 12)
+            }
+        }
+    }
+
+    /// The size of a texel block in bytes, which is a single texel for uncompressed formats.
+    pub fn texel_size(self) -> usize {
+        match self.layout() {
+            TexelLayout::Channels(channel, count) => channel.size() * count,
+            TexelLayout::Compressed { size, .. } => size,
+            _ => 4,
+        }
+    }
+
+    /// The width and height of a texel block.
+    pub fn block_dimensions(self) -> (u32, u32) {
+        match self.layout() {
+            TexelLayout::Compressed { width, height, .. } => (width, height),
+            _ => (1, 1),
+        }
+    }
+
+    pub fn is_compressed(self) -> bool {
+        matches!(self.layout(), TexelLayout::Compressed { .. })
+    }
+
+    pub fn is_srgb(self) -> bool {
+        self.as_str().ends_with("-srgb")
+    }
+}
+
+fn unorm(v: f32, bits: u32) -> u32 {
+    let max = ((1u64 << bits) - 1) as f32;
+    (v.clamp(0.0, 1.0) * max).round() as u32
+}
+
+fn linear_to_srgb(v: f32) -> f32 {
+    if v <= 0.0031308 {
+        v * 12.92
+    } else {
+        1.055 * v.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+fn srgb_to_linear(v: f32) -> f32 {
+    if v <= 0.04045 {
+        v / 12.92
+    } else {
+        ((v + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn round_shift(v: u32, shift: u32) -> u32 {
+    if shift >= 32 {
+        return 0;
+    }
+    if shift == 0 {
+        return v;
+    }
+    let q = v >> shift;
+    let r = v & ((1 << shift) - 1);
+    let half = 1 << (shift - 1);
+    if r > half || (r == half && q & 1 == 1) {
+        q + 1
+    } else {
+        q
+    }
+}
+
+/// Encode an unsigned float with a 5 bit exponent, as used by `rg11b10ufloat`.
+fn encode_ufloat(v: f32, mantissa_bits: u32) -> u32 {
+    let infinity = 0x1F << mantissa_bits;
+    if v.is_nan() {
+        return infinity | 1;
+    }
+    if v <= 0.0 {
+        return 0;
+    }
+    let bits = v.to_bits();
+    let exponent = ((bits >> 23) & 0xFF) as i32 - 127 + 15;
+    let mantissa = bits & 0x7F_FFFF;
+    if exponent >= 31 {
+        return infinity;
+    }
+    if exponent <= 0 {
+        let shift = (23 - mantissa_bits as i32 + 1 - exponent) as u32;
+        return round_shift(mantissa | 0x80_0000, shift);
+    }
+    // A carry out of the mantissa correctly increments the exponent.
+    ((exponent as u32) << mantissa_bits) + round_shift(mantissa, 23 - mantissa_bits)
+}
+
+fn decode_ufloat(v: u32, mantissa_bits: u32) -> f32 {
+    let exponent = (v >> mantissa_bits) as i32;
+    let mantissa = (v & ((1 << mantissa_bits) - 1)) as f32 / (1 << mantissa_bits) as f32;
+    match exponent {
+        0 => mantissa * 2f32.powi(-14),
+        31 if mantissa == 0.0 => f32::INFINITY,
+        31 => f32::NAN,
+        _ => (1.0 + mantissa) * 2f32.powi(exponent - 15),
+    }
+}
+
+fn encode_rgb9e5(v: [f32; 3]) -> u32 {
+    const MANTISSA_BITS: i32 = 9;
+    const BIAS: i32 = 15;
+    const MAX: f64 = 511.0 / 512.0 * 65536.0;
+
+    let c = v.map(|c| {
+        if c.is_nan() {
+            0.0
+        } else {
+            (c as f64).clamp(0.0, MAX)
+        }
+    });
+    let max = c[0].max(c[1]).max(c[2]);
+    let mut exponent = (max.log2().floor() as i32).max(-BIAS - 1) + 1 + BIAS;
+    if (max / 2f64.powi(exponent - BIAS - MANTISSA_BITS) + 0.5).floor() as u32 == 1 << MANTISSA_BITS
+    {
+        exponent += 1;
+    }
+    let scale = 2f64.powi(exponent - BIAS - MANTISSA_BITS);
+    let [r, g, b] = c.map(|c| (c / scale + 0.5).floor() as u32);
+    r | g << 9 | b << 18 | (exponent as u32) << 27
+}
+
+fn decode_rgb9e5(v: u32) -> [f32; 3] {
+    let scale = 2f32.powi((v >> 27) as i32 - 15 - 9);
+    [v & 0x1FF, (v >> 9) & 0x1FF, (v >> 18) & 0x1FF].map(|c| c as f32 * scale)
+}
+
+fn encode_texel(layout: TexelLayout, [r, g, b, a]: [f32; 4], buffer: &mut Vec<u8>) {
+    let packed = match layout {
+        TexelLayout::Channels(channel, count) => {
+            for v in &[r, g, b, a][..count] {
+                channel.encode(*v, buffer);
+            }
+            return;
+        }
+        TexelLayout::Srgb => {
+            let [r, g, b] = [r, g, b].map(|v| unorm(linear_to_srgb(v), 8) as u8);
+            buffer.extend_from_slice(&[r, g, b, unorm(a, 8) as u8]);
+            return;
+        }
+        TexelLayout::Bgra => {
+            let [r, g, b, a] = [r, g, b, a].map(|v| unorm(v, 8) as u8);
+            buffer.extend_from_slice(&[b, g, r, a]);
+            return;
+        }
+        TexelLayout::BgraSrgb => {
+            let [r, g, b] = [r, g, b].map(|v| unorm(linear_to_srgb(v), 8) as u8);
+            buffer.extend_from_slice(&[b, g, r, unorm(a, 8) as u8]);
+            return;
+        }
+        TexelLayout::Rgb9e5Ufloat => encode_rgb9e5([r, g, b]),
+        TexelLayout::Rgb10a2Uint => {
+            let [r, g, b] = [r, g, b].map(|v| v.round().clamp(0.0, 1023.0) as u32);
+            r | g << 10 | b << 20 | (a.round().clamp(0.0, 3.0) as u32) << 30
+        }
+        TexelLayout::Rgb10a2Unorm => {
+            unorm(r, 10) | unorm(g, 10) << 10 | unorm(b, 10) << 20 | unorm(a, 2) << 30
+        }
+        TexelLayout::Rg11b10Ufloat => {
+            encode_ufloat(r, 6) | encode_ufloat(g, 6) << 11 | encode_ufloat(b, 5) << 22
+        }
+        TexelLayout::Compressed { .. } => unreachable!(),
+    };
+    buffer.extend_from_slice(&packed.to_le_bytes());
+}
+
+fn check_uncompressed(format: TextureFormat) -> Result<(), WebGPUSerializeError> {
+    if format.is_compressed() {
+        return Err(WebGPUSerializeError::new(
+            ErrorKind::Unsupported,
+            format!(
+                "compressed format {} cannot be encoded or decoded",
+                format.as_str()
+            ),
+        ));
+    }
+    Ok(())
+}
+
+fn decode_texel(layout: TexelLayout, bytes: &[u8]) -> [f32; 4] {
+    let mut texel = [0.0, 0.0, 0.0, 1.0];
+    let packed = || u32::from_le_bytes(bytes[..4].try_into().unwrap());
+    match layout {
+        TexelLayout::Channels(channel, count) => {
+            for (i, v) in texel.iter_mut().take(count).enumerate() {
+                *v = channel.decode(&bytes[i * channel.size()..]);
+            }
+        }
+        TexelLayout::Srgb => {
+            for (i, v) in texel.iter_mut().enumerate() {
+                *v = bytes[i] as f32 / 255.0;
+            }
+            for v in &mut texel[..3] {
+                *v = srgb_to_linear(*v);
+            }
+        }
+        TexelLayout::Bgra | TexelLayout::BgraSrgb => {
+            texel = [bytes[2], bytes[1], bytes[0], bytes[3]].map(|v| v as f32 / 255.0);
+            if let TexelLayout::BgraSrgb = layout {
+                for v in &mut texel[..3] {
+                    *v = srgb_to_linear(*v);
+                }
+            }
+        }
+        TexelLayout::Rgb9e5Ufloat => {
+            let [r, g, b] = decode_rgb9e5(packed());
+            texel = [r, g, b, 1.0];
+        }
+        TexelLayout::Rgb10a2Uint => {
+            let v = packed();
+            texel = [v & 0x3FF, (v >> 10) & 0x3FF, (v >> 20) & 0x3FF, v >> 30].map(|v| v as f32);
+        }
+        TexelLayout::Rgb10a2Unorm => {
+            let v = packed();
+            let [r, g, b] =
+                [v & 0x3FF, (v >> 10) & 0x3FF, (v >> 20) & 0x3FF].map(|v| v as f32 / 1023.0);
+            texel = [r, g, b, (v >> 30) as f32 / 3.0];
+        }
+        TexelLayout::Rg11b10Ufloat => {
+            let v = packed();
+            texel = [
+                decode_ufloat(v & 0x7FF, 6),
+                decode_ufloat((v >> 11) & 0x7FF, 6),
+                decode_ufloat(v >> 22, 5),
+                1.0,
+            ];
+        }
+        TexelLayout::Compressed { .. } => unreachable!(),
+    }
+    texel
+}
+
+/// Encode tightly packed texels.
+pub fn encode_texels(
+    format: TextureFormat,
+    pixels: &[vec4<f32>],
+) -> Result<Vec<u8>, WebGPUSerializeError> {
+    check_uncompressed(format)?;
+    let layout = format.layout();
+    let mut buffer = Vec::with_capacity(pixels.len() * format.texel_size());
+    for pixel in pixels {
+        encode_texel(layout, pixel.0, &mut buffer);
+    }
+    Ok(buffer)
+}
+
+/// Decode tightly packed texels, e.g. read back from a storage texture.
+pub fn decode_texels(
+    format: TextureFormat,
+    bytes: &[u8],
+) -> Result<Vec<vec4<f32>>, WebGPUSerializeError> {
+    check_uncompressed(format)?;
+    let size = format.texel_size();
+    if bytes.len() % size != 0 {
+        return Err(WebGPUSerializeError::new(
+            ErrorKind::Size,
+            format!(
+                "buffer size {} is not a multiple of the {} texel size {}",
+                bytes.len(),
+                format.as_str(),
+                size
+            ),
+        ));
+    }
+    let layout = format.layout();
+    Ok(bytes
+        .chunks_exact(size)
+        .map(|texel| vec4(decode_texel(layout, texel)))
+        .collect())
+}
@@ -1,233 +1,358 @@
 #![allow(non_camel_case_types)]
 
-use std::ops::{Deref, DerefMut};
+use std::ops::{
+    Add, AddAssign, Deref, DerefMut, Div, DivAssign, Index, IndexMut, Mul, MulAssign, Neg, Sub,
+    SubAssign,
+};
 
 use serde::ser::SerializeTupleStruct;
 use serde::{Serialize, Serializer};
 
 use crate::f16;
+use crate::layout::WgslLayout;
+use crate::scalar::{Float, Scalar};
 
-#[derive(Copy, Clone, Debug, Default)]
-pub struct vec2<T>(pub [T; 2]);
+/// A WGSL vector of `N` components, with `N` from 2 to 4.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Vector<T, const N: usize>(pub [T; N]);
 
-impl<T> From<[T; 2]> for vec2<T> {
-    fn from(value: [T; 2]) -> Self {
-        Self(value)
-    }
+pub type vec2<T> = Vector<T, 2>;
+pub type vec3<T> = Vector<T, 3>;
+pub type vec4<T> = Vector<T, 4>;
+
+pub const fn vec2<T>(value: [T; 2]) -> vec2<T> {
+    Vector(value)
 }
 
-impl<T> Deref for vec2<T> {
-    type Target = [T; 2];
+pub const fn vec3<T>(value: [T; 3]) -> vec3<T> {
+    Vector(value)
+}
 
-    fn deref(&self) -> &Self::Target {
-        &self.0
-    }
+pub const fn vec4<T>(value: [T; 4]) -> vec4<T> {
+    Vector(value)
 }
 
-impl<T> DerefMut for vec2<T> {
-    fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.0
-    }
+impl<T: Scalar, const N: usize> Vector<T, N> {
+    const NAME: &'static str = match N {
+        2..=4 => T::VECTOR_NAMES[N - 2],
+        _ => panic!("WGSL vectors have 2 to 4 components"),
+    };
 }
 
-impl Serialize for vec2<f16> {
+impl<T: Scalar, const N: usize> Serialize for Vector<T, N> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
-        let mut s = serializer.serialize_tuple_struct("vec2@f16", 2)?;
-        s.serialize_field(&self[0])?;
-        s.serialize_field(&self[1])?;
+        let mut s = serializer.serialize_tuple_struct(Self::NAME, N)?;
+        for i in &self.0 {
+            s.serialize_field(i)?;
+        }
         s.end()
     }
 }
 
-impl Serialize for vec2<i32> {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        let mut s = serializer.serialize_tuple_struct("vec2@i32", 2)?;
-        s.serialize_field(&self[0])?;
-        s.serialize_field(&self[1])?;
-        s.end()
+/// The alignment of a vector of `len` components aligned to `align`, where `vec3` is aligned as
+/// `vec4`.
+pub(crate) const fn vector_align(len: usize, align: usize) -> usize {
+    if len == 2 {
+        2 * align
+    } else {
+        4 * align
     }
 }
 
-impl Serialize for vec2<u32> {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        let mut s = serializer.serialize_tuple_struct("vec2@u32", 2)?;
-        s.serialize_field(&self[0])?;
-        s.serialize_field(&self[1])?;
-        s.end()
-    }
+impl<T: Scalar, const N: usize> WgslLayout for Vector<T, N> {
+    const SIZE: usize = N * T::SIZE;
+    const ALIGN: usize = vector_align(N, T::ALIGN);
+    const WGSL_TYPE_NAME: &'static str = T::VECTOR_TYPE_NAMES[N - 2];
 }
 
-impl Serialize for vec2<f32> {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        let mut s = serializer.serialize_tuple_struct("vec2@f32", 2)?;
-        s.serialize_field(&self[0])?;
-        s.serialize_field(&self[1])?;
-        s.end()
+impl<T: Default, const N: usize> Default for Vector<T, N> {
+    fn default() -> Self {
+        Self(std::array::from_fn(|_| T::default()))
     }
 }
 
-#[derive(Copy, Clone, Debug, Default)]
-pub struct vec3<T>(pub [T; 3]);
-
-impl<T> From<[T; 3]> for vec3<T> {
-    fn from(value: [T; 3]) -> Self {
+impl<T, const N: usize> From<[T; N]> for Vector<T, N> {
+    fn from(value: [T; N]) -> Self {
         Self(value)
     }
 }
 
-impl<T> Deref for vec3<T> {
-    type Target = [T; 3];
+impl<T, const N: usize> From<Vector<T, N>> for [T; N] {
+    fn from(value: Vector<T, N>) -> Self {
+        value.0
+    }
+}
+
+impl<T, const N: usize> Deref for Vector<T, N> {
+    type Target = [T; N];
 
     fn deref(&self) -> &Self::Target {
         &self.0
     }
 }
 
-impl<T> DerefMut for vec3<T> {
+impl<T, const N: usize> DerefMut for Vector<T, N> {
     fn deref_mut(&mut self) -> &mut Self::Target {
         &mut self.0
     }
 }
 
-impl Serialize for vec3<f16> {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        let mut s = serializer.serialize_tuple_struct("vec3@f16", 3)?;
-        s.serialize_field(&self[0])?;
-        s.serialize_field(&self[1])?;
-        s.serialize_field(&self[2])?;
-        s.end()
+impl<T, const N: usize> Index<usize> for Vector<T, N> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.0[index]
     }
 }
 
-impl Serialize for vec3<i32> {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        let mut s = serializer.serialize_tuple_struct("vec3@i32", 3)?;
-        s.serialize_field(&self[0])?;
-        s.serialize_field(&self[1])?;
-        s.serialize_field(&self[2])?;
-        s.end()
+impl<T, const N: usize> IndexMut<usize> for Vector<T, N> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.0[index]
     }
 }
 
-impl Serialize for vec3<u32> {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        let mut s = serializer.serialize_tuple_struct("vec3@u32", 3)?;
-        s.serialize_field(&self[0])?;
-        s.serialize_field(&self[1])?;
-        s.serialize_field(&self[2])?;
-        s.end()
+impl<T, const N: usize> IntoIterator for Vector<T, N> {
+    type Item = T;
+    type IntoIter = std::array::IntoIter<T, N>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
     }
 }
 
-impl Serialize for vec3<f32> {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        let mut s = serializer.serialize_tuple_struct("vec3@f32", 3)?;
-        s.serialize_field(&self[0])?;
-        s.serialize_field(&self[1])?;
-        s.serialize_field(&self[2])?;
-        s.end()
+impl<'a, T, const N: usize> IntoIterator for &'a Vector<T, N> {
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
     }
 }
 
-#[derive(Copy, Clone, Debug, Default)]
-pub struct vec4<T>(pub [T; 4]);
+impl<T: Copy + Neg<Output = T>, const N: usize> Neg for Vector<T, N> {
+    type Output = Self;
 
-impl<T> From<[T; 4]> for vec4<T> {
-    fn from(value: [T; 4]) -> Self {
-        Self(value)
+    fn neg(self) -> Self::Output {
+        Self(self.0.map(|v| -v))
     }
 }
 
-impl<T> Deref for vec4<T> {
-    type Target = [T; 4];
+impl<T: Float, const N: usize> Vector<T, N> {
+    pub fn dot(self, rhs: Self) -> T {
+        (0..N).fold(T::ZERO, |sum, i| sum + self.0[i] * rhs.0[i])
+    }
 
-    fn deref(&self) -> &Self::Target {
-        &self.0
+    pub fn length(self) -> T {
+        self.dot(self).sqrt()
     }
-}
 
-impl<T> DerefMut for vec4<T> {
-    fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.0
+    pub fn distance(self, rhs: Self) -> T {
+        (self - rhs).length()
     }
-}
 
-impl Serialize for vec4<f16> {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        let mut s = serializer.serialize_tuple_struct("vec4@f16", 4)?;
-        s.serialize_field(&self[0])?;
-        s.serialize_field(&self[1])?;
-        s.serialize_field(&self[2])?;
-        s.serialize_field(&self[3])?;
-        s.end()
+    /// The unit vector in the same direction, NaN for a zero vector like WGSL.
+    pub fn normalize(self) -> Self {
+        self / self.length()
     }
 }
 
-impl Serialize for vec4<i32> {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        let mut s = serializer.serialize_tuple_struct("vec4@i32", 4)?;
-        s.serialize_field(&self[0])?;
-        s.serialize_field(&self[1])?;
-        s.serialize_field(&self[2])?;
-        s.serialize_field(&self[3])?;
-        s.end()
+impl<T: Float> vec3<T> {
+    pub fn cross(self, rhs: Self) -> Self {
+        let [a, b] = [self.0, rhs.0];
+        vec3([
+            a[1] * b[2] - a[2] * b[1],
+            a[2] * b[0] - a[0] * b[2],
+            a[0] * b[1] - a[1] * b[0],
+        ])
     }
 }
 
-impl Serialize for vec4<u32> {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        let mut s = serializer.serialize_tuple_struct("vec4@u32", 4)?;
-        s.serialize_field(&self[0])?;
-        s.serialize_field(&self[1])?;
-        s.serialize_field(&self[2])?;
-        s.serialize_field(&self[3])?;
-        s.end()
-    }
+/// Component-wise operators, with a vector or a scalar on the right.
+macro_rules! vector_op {
+    ($op:ident, $fn:ident, $op_assign:ident, $fn_assign:ident) => {
+        impl<T: Copy + $op<Output = T>, const N: usize> $op for Vector<T, N> {
+            type Output = Self;
+
+            fn $fn(self, rhs: Self) -> Self::Output {
+                Self(std::array::from_fn(|i| self.0[i].$fn(rhs.0[i])))
+            }
+        }
+
+        impl<T: Copy + $op<Output = T>, const N: usize> $op<T> for Vector<T, N> {
+            type Output = Self;
+
+            fn $fn(self, rhs: T) -> Self::Output {
+                Self(self.0.map(|v| v.$fn(rhs)))
+            }
+        }
+
+        impl<T: Copy + $op<Output = T>, const N: usize> $op_assign for Vector<T, N> {
+            fn $fn_assign(&mut self, rhs: Self) {
+                *self = (*self).$fn(rhs);
+            }
+        }
+
+        impl<T: Copy + $op<Output = T>, const N: usize> $op_assign<T> for Vector<T, N> {
+            fn $fn_assign(&mut self, rhs: T) {
+                *self = (*self).$fn(rhs);
+            }
+        }
+    };
 }
 
-impl Serialize for vec4<f32> {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        let mut s = serializer.serialize_tuple_struct("vec4@f32", 4)?;
-        s.serialize_field(&self[0])?;
-        s.serialize_field(&self[1])?;
-        s.serialize_field(&self[2])?;
-        s.serialize_field(&self[3])?;
-        s.end()
+vector_op!(Add, add, AddAssign, add_assign);
+vector_op!(Sub, sub, SubAssign, sub_assign);
+vector_op!(Mul, mul, MulAssign, mul_assign);
+vector_op!(Div, div, DivAssign, div_assign);
+
+/// Operators with a scalar on the left, which cannot be generic over the scalar.
+macro_rules! scalar_op {
+    ($($t:ident),*) => {
+        $(
+            impl<const N: usize> Add<Vector<$t, N>> for $t {
+                type Output = Vector<$t, N>;
+
+                fn add(self, rhs: Vector<$t, N>) -> Self::Output {
+                    Vector(rhs.0.map(|v| self + v))
+                }
+            }
+
+            impl<const N: usize> Sub<Vector<$t, N>> for $t {
+                type Output = Vector<$t, N>;
+
+                fn sub(self, rhs: Vector<$t, N>) -> Self::Output {
+                    Vector(rhs.0.map(|v| self - v))
+                }
+            }
+
+            impl<const N: usize> Mul<Vector<$t, N>> for $t {
+                type Output = Vector<$t, N>;
+
+                fn mul(self, rhs: Vector<$t, N>) -> Self::Output {
+                    Vector(rhs.0.map(|v| self * v))
+                }
+            }
+
+            impl<const N: usize> Div<Vector<$t, N>> for $t {
+                type Output = Vector<$t, N>;
+
+                fn div(self, rhs: Vector<$t, N>) -> Self::Output {
+                    Vector(rhs.0.map(|v| self / v))
+                }
+            }
+        )*
+    };
+}
+
+scalar_op!(f16, i32, u32, f32, i64, u64, f64);
+
+macro_rules! tuple {
+    ($name:ident, ($($c:ident),*)) => {
+        impl<T> From<($(component!($c),)*)> for $name<T> {
+            fn from(value: ($(component!($c),)*)) -> Self {
+                let ($($c,)*) = value;
+                Vector([$($c),*])
+            }
+        }
+
+        impl<T> From<$name<T>> for ($(component!($c),)*) {
+            fn from(value: $name<T>) -> Self {
+                let [$($c),*] = value.0;
+                ($($c,)*)
+            }
+        }
+    };
+}
+
+macro_rules! component {
+    ($c:ident) => {
+        T
+    };
+}
+
+tuple!(vec2, (x, y));
+tuple!(vec3, (x, y, z));
+tuple!(vec4, (x, y, z, w));
+
+/// WGSL swizzles, such as `v.zyx()`, grouped by the result type. The `rgba` names are left out,
+/// since they only repeat the `xyzw` ones.
+macro_rules! swizzle {
+    ($name:ident { $($out:ident: $($fn:ident)*;)* }) => {
+        impl<T: Copy> $name<T> {
+            $($(
+                pub fn $fn(self) -> $out<T> {
+                    Vector(swizzle_indices(stringify!($fn)).map(|i| self.0[i]))
+                }
+            )*)*
+        }
+    };
+}
+
+/// The component indices spelled by a swizzle, such as `[2, 1, 0]` for `zyx`.
+const fn swizzle_indices<const N: usize>(name: &str) -> [usize; N] {
+    let name = name.as_bytes();
+    let mut indices = [0; N];
+    let mut i = 0;
+    while i < N {
+        indices[i] = match name[i] {
+            b'x' => 0,
+            b'y' => 1,
+            b'z' => 2,
+            b'w' => 3,
+            _ => panic!("invalid swizzle"),
+        };
+        i += 1;
     }
+    indices
 }
+
+swizzle!(vec2 {
+    vec2:
+        xx xy yx yy;
+    vec3:
+        xxx xxy xyx xyy yxx yxy yyx yyy;
+    vec4:
+        xxxx xxxy xxyx xxyy xyxx xyxy xyyx xyyy yxxx yxxy yxyx yxyy yyxx yyxy yyyx yyyy;
+});
+
+swizzle!(vec3 {
+    vec2:
+        xx xy xz yx yy yz zx zy zz;
+    vec3:
+        xxx xxy xxz xyx xyy xyz xzx xzy xzz yxx yxy yxz yyx yyy yyz yzx yzy yzz zxx zxy zxz zyx zyy
+        zyz zzx zzy zzz;
+    vec4:
+        xxxx xxxy xxxz xxyx xxyy xxyz xxzx xxzy xxzz xyxx xyxy xyxz xyyx xyyy xyyz xyzx xyzy xyzz
+        xzxx xzxy xzxz xzyx xzyy xzyz xzzx xzzy xzzz yxxx yxxy yxxz yxyx yxyy yxyz yxzx yxzy yxzz
+        yyxx yyxy yyxz yyyx yyyy yyyz yyzx yyzy yyzz yzxx yzxy yzxz yzyx yzyy yzyz yzzx yzzy yzzz
+        zxxx zxxy zxxz zxyx zxyy zxyz zxzx zxzy zxzz zyxx zyxy zyxz zyyx zyyy zyyz zyzx zyzy zyzz
+        zzxx zzxy zzxz zzyx zzyy zzyz zzzx zzzy zzzz;
+});
+
+swizzle!(vec4 {
+    vec2:
+        xx xy xz xw yx yy yz yw zx zy zz zw wx wy wz ww;
+    vec3:
+        xxx xxy xxz xxw xyx xyy xyz xyw xzx xzy xzz xzw xwx xwy xwz xww yxx yxy yxz yxw yyx yyy yyz
+        yyw yzx yzy yzz yzw ywx ywy ywz yww zxx zxy zxz zxw zyx zyy zyz zyw zzx zzy zzz zzw zwx zwy
+        zwz zww wxx wxy wxz wxw wyx wyy wyz wyw wzx wzy wzz wzw wwx wwy wwz www;
+    vec4:
+        xxxx xxxy xxxz xxxw xxyx xxyy xxyz xxyw xxzx xxzy xxzz xxzw xxwx xxwy xxwz xxww xyxx xyxy
+        xyxz xyxw xyyx xyyy xyyz xyyw xyzx xyzy xyzz xyzw xywx xywy xywz xyww xzxx xzxy xzxz xzxw
+        xzyx xzyy xzyz xzyw xzzx xzzy xzzz xzzw xzwx xzwy xzwz xzww xwxx xwxy xwxz xwxw xwyx xwyy
+        xwyz xwyw xwzx xwzy xwzz xwzw xwwx xwwy xwwz xwww yxxx yxxy yxxz yxxw yxyx yxyy yxyz yxyw
+        yxzx yxzy yxzz yxzw yxwx yxwy yxwz yxww yyxx yyxy yyxz yyxw yyyx yyyy yyyz yyyw yyzx yyzy
+        yyzz yyzw yywx yywy yywz yyww yzxx yzxy yzxz yzxw yzyx yzyy yzyz yzyw yzzx yzzy yzzz yzzw
+        yzwx yzwy yzwz yzww ywxx ywxy ywxz ywxw ywyx ywyy ywyz ywyw ywzx ywzy ywzz ywzw ywwx ywwy
+        ywwz ywww zxxx zxxy zxxz zxxw zxyx zxyy zxyz zxyw zxzx zxzy zxzz zxzw zxwx zxwy zxwz zxww
+        zyxx zyxy zyxz zyxw zyyx zyyy zyyz zyyw zyzx zyzy zyzz zyzw zywx zywy zywz zyww zzxx zzxy
+        zzxz zzxw zzyx zzyy zzyz zzyw zzzx zzzy zzzz zzzw zzwx zzwy zzwz zzww zwxx zwxy zwxz zwxw
+        zwyx zwyy zwyz zwyw zwzx zwzy zwzz zwzw zwwx zwwy zwwz zwww wxxx wxxy wxxz wxxw wxyx wxyy
+        wxyz wxyw wxzx wxzy wxzz wxzw wxwx wxwy wxwz wxww wyxx wyxy wyxz wyxw wyyx wyyy wyyz wyyw
+        wyzx wyzy wyzz wyzw wywx wywy wywz wyww wzxx wzxy wzxz wzxw wzyx wzyy wzyz wzyw wzzx wzzy
+        wzzz wzzw wzwx wzwy wzwz wzww wwxx wwxy wwxz wwxw wwyx wwyy wwyz wwyw wwzx wwzy wwzz wwzw
+        wwwx wwwy wwwz wwww;
+});
@@ -0,0 +1,25 @@
+use serde::Serialize;
+
+use serde_webgpu::serialize_webgpu;
+use serde_webgpu::vec::vec3;
+
+#[test]
+fn vec3_member_offsets() {
+    #[derive(Serialize)]
+    struct Light {
+        intensity: f32,
+        position: vec3<f32>,
+        range: f32,
+    }
+
+    let light = Light {
+        intensity: 1.0,
+        position: vec3([2.0; 3]),
+        range: 3.0,
+    };
+    let buffer = serialize_webgpu(&light).unwrap();
+    // `position` starts on 16 bytes, and `range` fills the 4 bytes after it.
+    assert_eq!(buffer.len(), 32);
+    assert_eq!(buffer[16..20], 2.0f32.to_le_bytes());
+    assert_eq!(buffer[28..32], 3.0f32.to_le_bytes());
+}
@@ -0,0 +1,35 @@
+use std::sync::atomic::AtomicU32;
+
+use serde::Serialize;
+
+use serde_webgpu::atomic::atomic;
+use serde_webgpu::{serialize_webgpu, serialize_webgpu_with, AddressSpace, WebGPUConfig};
+
+#[derive(Serialize)]
+struct Counters {
+    count: atomic<u32>,
+    min: atomic<i32>,
+}
+
+#[test]
+fn storage() {
+    let count = AtomicU32::new(7);
+    let counters = Counters {
+        count: atomic::from(&count),
+        min: atomic(-1),
+    };
+
+    let config = WebGPUConfig {
+        address_space: AddressSpace::Storage,
+        ..Default::default()
+    };
+    assert_eq!(
+        serialize_webgpu_with(&counters, &config).unwrap(),
+        [7, 0, 0, 0, 0xFF, 0xFF, 0xFF, 0xFF]
+    );
+}
+
+#[test]
+fn uniform() {
+    assert!(serialize_webgpu(&atomic(1u32)).is_err());
+}
@@ -1,6 +1,7 @@
 use half::f16;
 use serde::Serialize;
 
+use serde_webgpu::dump::dump_webgpu;
 use serde_webgpu::mat::mat4x4;
 use serde_webgpu::serialize_webgpu_buffer;
 use serde_webgpu::vec::vec4;
@@ -15,14 +16,16 @@ fn base() {
 
     let uniform = Uniform {
         a: f16::from_f32(123.456),
-        b: [
+        b: mat4x4([
             vec4([1.0, 2.0, 3.0, 4.0]),
             vec4([4.0, 5.0, 7.0, 8.0]),
             vec4([1.0, 2.0, 3.0, 4.0]),
             vec4([5.0, 6.0, 7.0, 8.0]),
-        ],
+        ]),
     };
 
     let buffer = serialize_webgpu_buffer(&uniform).unwrap();
-    println!("{:#?}", buffer);
+    let dump = dump_webgpu(&uniform).unwrap();
+    assert_eq!(dump.bytes(), buffer);
+    assert_eq!(dump.field("b").unwrap().offset, 16);
 }
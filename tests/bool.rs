@@ -0,0 +1,54 @@
+use serde::Serialize;
+
+use serde_webgpu::{serialize_webgpu, serialize_webgpu_with, BoolPolicy, WebGPUConfig};
+
+#[derive(Serialize)]
+struct Flags {
+    a: bool,
+    b: bool,
+    c: f32,
+    d: bool,
+    e: [bool; 3],
+}
+
+fn flags() -> Flags {
+    Flags {
+        a: true,
+        b: true,
+        c: 1.0,
+        d: false,
+        e: [false, true, true],
+    }
+}
+
+fn config(bool_policy: BoolPolicy) -> WebGPUConfig {
+    WebGPUConfig {
+        bool_policy,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn widen() {
+    let buffer = serialize_webgpu(&flags()).unwrap();
+    assert_eq!(buffer.len(), 28);
+    assert_eq!(buffer[..8], [1, 0, 0, 0, 1, 0, 0, 0]);
+    assert_eq!(buffer[20..24], [1, 0, 0, 0]);
+}
+
+#[test]
+fn pack() {
+    let buffer = serialize_webgpu_with(&flags(), &config(BoolPolicy::Pack)).unwrap();
+    assert_eq!(
+        buffer,
+        [0b11, 0, 0, 0, 0, 0, 0x80, 0x3F, 0, 0, 0, 0, 0b110, 0, 0, 0]
+    );
+
+    let buffer = serialize_webgpu_with(&vec![true; 33], &config(BoolPolicy::Pack)).unwrap();
+    assert_eq!(buffer, [0xFF, 0xFF, 0xFF, 0xFF, 1, 0, 0, 0]);
+}
+
+#[test]
+fn reject() {
+    assert!(serialize_webgpu_with(&flags(), &config(BoolPolicy::Reject)).is_err());
+}
@@ -0,0 +1,55 @@
+use serde_webgpu::copy::TextureCopyLayout;
+use serde_webgpu::texture::TextureFormat;
+
+#[test]
+fn mip_chain() {
+    let layout = TextureCopyLayout::new(TextureFormat::Rgba16Float, 70, 33, 7, 2).unwrap();
+    assert_eq!(layout.subresources().len(), 14);
+
+    let first = layout.subresource(0, 1).unwrap();
+    assert_eq!(first.tight_bytes_per_row, 560);
+    assert_eq!(first.bytes_per_row, 768);
+    assert_eq!(first.rows_per_image, 33);
+    assert_eq!(first.offset, 768 * 33);
+
+    let last = layout.subresource(6, 0).unwrap();
+    assert_eq!((last.width, last.height), (1, 1));
+    assert_eq!(last.bytes_per_row, 256);
+    for i in layout.subresources() {
+        assert_eq!(i.offset % 256, 0);
+    }
+
+    let tight: Vec<u8> = (0..layout.tight_size()).map(|i| i as u8).collect();
+    let padded = layout.pad(&tight).unwrap();
+    assert_eq!(padded.len(), layout.size());
+    assert_eq!(padded[..560], tight[..560]);
+    assert!(padded[560..768].iter().all(|&i| i == 0));
+    assert_eq!(layout.unpad(&padded).unwrap(), tight);
+}
+
+#[test]
+fn compressed() {
+    let layout = TextureCopyLayout::new(TextureFormat::Bc1RgbaUnorm, 10, 10, 3, 1).unwrap();
+    let mip = layout.subresource(0, 0).unwrap();
+    assert_eq!(mip.tight_bytes_per_row, 3 * 8);
+    assert_eq!(mip.rows_per_image, 3);
+
+    // 2x2 and 1x1 mip levels still take a whole block.
+    let mip = layout.subresource(2, 0).unwrap();
+    assert_eq!((mip.width, mip.height), (2, 2));
+    assert_eq!(mip.tight_bytes_per_row, 8);
+    assert_eq!(mip.rows_per_image, 1);
+
+    let layout = TextureCopyLayout::new(TextureFormat::Astc10x6Unorm, 20, 20, 1, 1).unwrap();
+    assert_eq!(layout.tight_size(), 2 * 4 * 16);
+}
+
+#[test]
+fn invalid() {
+    assert!(TextureCopyLayout::new(TextureFormat::R8Unorm, 0, 4, 1, 1).is_err());
+    assert!(TextureCopyLayout::new(TextureFormat::R8Unorm, 4, 4, 4, 1).is_err());
+
+    let layout = TextureCopyLayout::new(TextureFormat::R8Unorm, 4, 4, 3, 1).unwrap();
+    assert!(layout.pad(&[0; 3]).is_err());
+    assert!(layout.unpad(&[0; 3]).is_err());
+}
@@ -0,0 +1,86 @@
+use half::f16;
+use serde::Serialize;
+
+use serde_webgpu::atomic::atomic;
+use serde_webgpu::dump::{dump_webgpu, dump_webgpu_with};
+use serde_webgpu::mat::mat2x2;
+use serde_webgpu::vec::{vec2, vec3};
+use serde_webgpu::{AddressSpace, BoolPolicy, WebGPUConfig};
+
+#[test]
+fn fields() {
+    #[derive(Serialize)]
+    struct Light {
+        color: vec3<f32>,
+        range: f16,
+    }
+
+    #[derive(Serialize)]
+    struct Scene {
+        count: atomic<u32>,
+        lights: [Light; 2],
+        basis: mat2x2<f32>,
+        visible: bool,
+        shadows: bool,
+    }
+
+    let scene = Scene {
+        count: atomic(2),
+        lights: [
+            Light {
+                color: vec3([1.0; 3]),
+                range: f16::ONE,
+            },
+            Light {
+                color: vec3([0.5; 3]),
+                range: f16::ONE,
+            },
+        ],
+        basis: mat2x2([vec2([1.0, 0.0]), vec2([0.0, 1.0])]),
+        visible: true,
+        shadows: true,
+    };
+    let config = WebGPUConfig {
+        address_space: AddressSpace::Storage,
+        bool_policy: BoolPolicy::Pack,
+        ..Default::default()
+    };
+    let dump = dump_webgpu_with(&scene, &config).unwrap();
+    let fields: Vec<_> = dump
+        .fields()
+        .iter()
+        .map(|i| (i.path.as_str(), i.wgsl_type.as_str(), i.offset, i.size))
+        .collect();
+    assert_eq!(
+        fields,
+        [
+            ("count", "atomic<u32>", 0, 4),
+            ("lights[0].color", "vec3<f32>", 16, 12),
+            ("lights[0].range", "f16", 28, 2),
+            ("lights[1].color", "vec3<f32>", 32, 12),
+            ("lights[1].range", "f16", 44, 2),
+            ("basis", "mat2x2<f32>", 48, 16),
+            ("visible", "bool in bit 0 of u32", 64, 4),
+            ("shadows", "bool in bit 1 of u32", 64, 4),
+        ]
+    );
+    assert_eq!(dump.bytes().len(), 80);
+    assert_eq!(dump.padding_ranges(), [4..16, 30..32, 46..48, 68..80]);
+}
+
+#[test]
+fn display() {
+    let dump = dump_webgpu(&(1u32, 0xABu8)).unwrap();
+    let lines: Vec<_> = dump.to_string().lines().map(str::to_string).collect();
+    assert_eq!(
+        lines,
+        [
+            "     0..4       [0]: u32",
+            "               01 00 00 00",
+            "     4..5       [1]: u8",
+            "               ab",
+            "     5..8       padding",
+            "               00 00 00",
+        ]
+    );
+}
@@ -0,0 +1,60 @@
+use serde::Serialize;
+
+use serde_webgpu::enums::WgslEnum;
+use serde_webgpu::{serialize_webgpu, serialize_webgpu_with, wgsl_enum, LayoutMode, WebGPUConfig};
+
+#[derive(Serialize)]
+enum BlendMode {
+    Opaque,
+    Alpha,
+    Additive,
+}
+
+wgsl_enum! {
+    /// Explicit discriminants.
+    #[derive(Copy, Clone, Debug, Eq, PartialEq)]
+    enum ShaderMode {
+        PBRLit = 3,
+        Unlit,
+        #[allow(dead_code)]
+        Debug2D = 10,
+    }
+}
+
+#[test]
+fn variant_index() {
+    #[derive(Serialize)]
+    struct Material {
+        blend: BlendMode,
+        opacity: f32,
+    }
+
+    let material = Material {
+        blend: BlendMode::Additive,
+        opacity: 0.5,
+    };
+    assert_eq!(serialize_webgpu(&material).unwrap()[..4], [2, 0, 0, 0]);
+    assert_eq!(serialize_webgpu(&BlendMode::Opaque).unwrap(), [0, 0, 0, 0]);
+    assert_eq!(serialize_webgpu(&BlendMode::Alpha).unwrap(), [1, 0, 0, 0]);
+}
+
+#[test]
+fn discriminant() {
+    assert_eq!(serialize_webgpu(&ShaderMode::PBRLit).unwrap(), [3, 0, 0, 0]);
+    assert_eq!(serialize_webgpu(&ShaderMode::Unlit).unwrap(), [4, 0, 0, 0]);
+    // A scalar, not a struct padded to 16 bytes.
+    let std140 = WebGPUConfig {
+        layout: LayoutMode::Std140,
+        ..Default::default()
+    };
+    assert_eq!(
+        serialize_webgpu_with(&ShaderMode::Unlit, &std140).unwrap(),
+        [4, 0, 0, 0]
+    );
+    assert_eq!(
+        ShaderMode::wgsl_consts(),
+        "const SHADER_MODE_PBR_LIT: u32 = 3u;\n\
+         const SHADER_MODE_UNLIT: u32 = 4u;\n\
+         const SHADER_MODE_DEBUG2D: u32 = 10u;\n"
+    );
+}
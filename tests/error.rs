@@ -0,0 +1,67 @@
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+use serde_webgpu::vec::vec3;
+use serde_webgpu::{serialize_webgpu, ErrorKind};
+
+#[derive(Serialize)]
+enum LightKind {
+    Point,
+    Spot { angle: f32 },
+}
+
+#[derive(Serialize)]
+struct Light {
+    position: vec3<f32>,
+    kind: LightKind,
+}
+
+#[derive(Serialize)]
+struct Scene {
+    ambient: vec3<f32>,
+    lights: [Light; 3],
+}
+
+#[derive(Serialize)]
+struct Empty {}
+
+#[test]
+fn path() {
+    let light = |kind| Light {
+        position: vec3([0.0; 3]),
+        kind,
+    };
+    let scene = Scene {
+        ambient: vec3([0.1; 3]),
+        lights: [
+            light(LightKind::Point),
+            light(LightKind::Point),
+            light(LightKind::Spot { angle: 0.5 }),
+        ],
+    };
+    let error = serialize_webgpu(&scene).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::Unsupported);
+    assert_eq!(error.path(), Some("lights[2].kind"));
+    assert_eq!(error.type_name(), Some("LightKind"));
+    assert_eq!(error.offset(), Some(60));
+    assert_eq!(
+        error.to_string(),
+        "enum with fields is only supported as a tagged union \
+         (at `lights[2].kind`, type `LightKind`, offset 60)"
+    );
+}
+
+#[test]
+fn type_name() {
+    let error = serialize_webgpu(&(1u32, Empty {})).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::ZeroSize);
+    assert_eq!(error.path(), Some("[1]"));
+    assert_eq!(error.type_name(), Some("Empty"));
+
+    let error = serialize_webgpu(&BTreeMap::from([(1u32, 2u32)])).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::Unsupported);
+    let error = serialize_webgpu(&Some(1u32)).unwrap_err();
+    assert_eq!(error.type_name(), Some("Option"));
+    assert_eq!(error.path(), None);
+}
@@ -0,0 +1,19 @@
+use half::f16;
+
+use serde_webgpu::serialize_webgpu;
+use serde_webgpu::vec::vec2;
+
+#[test]
+fn f16_alignment() {
+    // `f16` is aligned to 2 bytes, unlike a plain `u16`.
+    assert_eq!(
+        serialize_webgpu(&vec2([f16::ONE, f16::ONE])).unwrap().len(),
+        4
+    );
+    assert_eq!(
+        serialize_webgpu(&(f16::ONE, f16::ONE, 1.0f32))
+            .unwrap()
+            .len(),
+        8
+    );
+}
@@ -0,0 +1,83 @@
+use serde::Serialize;
+
+use serde_webgpu::mat::mat3x3;
+use serde_webgpu::vec::{vec2, vec3};
+use serde_webgpu::{serialize_webgpu_with, LayoutMode, WebGPUConfig};
+
+#[derive(Serialize)]
+struct Constants {
+    a: [f32; 2],
+    b: f32,
+    c: vec3<f32>,
+    d: vec2<f32>,
+    m: mat3x3<f32>,
+    e: f32,
+}
+
+fn config(layout: LayoutMode) -> WebGPUConfig {
+    WebGPUConfig {
+        layout,
+        ..Default::default()
+    }
+}
+
+fn constants() -> Constants {
+    Constants {
+        a: [1.0; 2],
+        b: 2.0,
+        c: vec3([3.0; 3]),
+        d: vec2([4.0; 2]),
+        m: mat3x3::identity(),
+        e: 5.0,
+    }
+}
+
+#[test]
+fn cbuffer() {
+    let bytes = serialize_webgpu_with(&constants(), &config(LayoutMode::HlslCbuffer)).unwrap();
+    assert_eq!(bytes.len(), 112);
+    // Array elements start on a register, but `b` packs after the last one.
+    assert_eq!(bytes[16..20], 1.0f32.to_le_bytes());
+    assert_eq!(bytes[20..24], 2.0f32.to_le_bytes());
+    // Vectors move to the next register instead of straddling one.
+    assert_eq!(bytes[32..36], 3.0f32.to_le_bytes());
+    assert_eq!(bytes[48..52], 4.0f32.to_le_bytes());
+    // Matrix columns start on a register, and `e` packs after the last one.
+    assert_eq!(bytes[64..68], 1.0f32.to_le_bytes());
+    assert_eq!(bytes[84..88], 1.0f32.to_le_bytes());
+    assert_eq!(bytes[104..108], 1.0f32.to_le_bytes());
+    assert_eq!(bytes[108..112], 5.0f32.to_le_bytes());
+}
+
+#[test]
+fn cbuffer_struct() {
+    #[derive(Serialize)]
+    struct Inner {
+        a: f32,
+    }
+
+    #[derive(Serialize)]
+    struct Outer {
+        x: f32,
+        inner: Inner,
+        y: f32,
+    }
+
+    let outer = Outer {
+        x: 1.0,
+        inner: Inner { a: 2.0 },
+        y: 3.0,
+    };
+    let bytes = serialize_webgpu_with(&outer, &config(LayoutMode::HlslCbuffer)).unwrap();
+    assert_eq!(bytes.len(), 48);
+    assert_eq!(bytes[16..20], 2.0f32.to_le_bytes());
+    assert_eq!(bytes[32..36], 3.0f32.to_le_bytes());
+}
+
+#[test]
+fn structured() {
+    let bytes = serialize_webgpu_with(&constants(), &config(LayoutMode::HlslStructured)).unwrap();
+    assert_eq!(bytes.len(), 4 * (2 + 1 + 3 + 2 + 9 + 1));
+    assert_eq!(bytes[12..16], 3.0f32.to_le_bytes());
+    assert_eq!(bytes[68..72], 5.0f32.to_le_bytes());
+}
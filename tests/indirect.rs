@@ -0,0 +1,62 @@
+use serde_webgpu::indirect::{
+    decode_indirect_args, serialize_indirect_args, DispatchIndirectArgs, DrawIndexedIndirectArgs,
+    DrawIndirectArgs,
+};
+use serde_webgpu::serialize_webgpu;
+
+#[test]
+fn layout() {
+    let draw = DrawIndirectArgs {
+        vertex_count: 3,
+        instance_count: 2,
+        first_vertex: 1,
+        first_instance: 0,
+    };
+    assert_eq!(
+        serialize_webgpu(&draw).unwrap(),
+        [3, 0, 0, 0, 2, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0]
+    );
+
+    let draw = DrawIndexedIndirectArgs {
+        base_vertex: -1,
+        ..Default::default()
+    };
+    let buffer = serialize_webgpu(&draw).unwrap();
+    assert_eq!(buffer.len(), 20);
+    assert_eq!(buffer[12..16], [0xFF; 4]);
+
+    let dispatch = DispatchIndirectArgs { x: 8, y: 4, z: 1 };
+    assert_eq!(serialize_webgpu(&dispatch).unwrap().len(), 12);
+}
+
+#[test]
+fn round_trip() {
+    let dispatches = [
+        DispatchIndirectArgs { x: 1, y: 2, z: 3 },
+        DispatchIndirectArgs { x: 4, y: 5, z: 6 },
+    ];
+    let buffer = serialize_indirect_args(&dispatches).unwrap();
+    assert_eq!(buffer.len(), 24);
+    assert_eq!(
+        decode_indirect_args::<DispatchIndirectArgs>(&buffer).unwrap(),
+        dispatches
+    );
+
+    let draws = [DrawIndexedIndirectArgs {
+        index_count: 6,
+        instance_count: 1,
+        first_index: 12,
+        base_vertex: -4,
+        first_instance: 7,
+    }];
+    let buffer = serialize_indirect_args(&draws).unwrap();
+    assert_eq!(
+        decode_indirect_args::<DrawIndexedIndirectArgs>(&buffer).unwrap(),
+        draws
+    );
+
+    assert!(serialize_indirect_args::<DrawIndirectArgs>(&[])
+        .unwrap()
+        .is_empty());
+    assert!(decode_indirect_args::<DrawIndirectArgs>(&[0; 20]).is_err());
+}
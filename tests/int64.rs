@@ -0,0 +1,63 @@
+use serde::Serialize;
+
+use serde_webgpu::mat::mat3x3;
+use serde_webgpu::vec::{vec2, vec3};
+use serde_webgpu::{serialize_webgpu, serialize_webgpu_with, ShaderExtensions, WebGPUConfig};
+
+fn config(int64: bool, f64: bool) -> WebGPUConfig {
+    WebGPUConfig {
+        extensions: ShaderExtensions { int64, f64 },
+        ..Default::default()
+    }
+}
+
+#[test]
+fn scalar() {
+    #[derive(Serialize)]
+    struct Data {
+        a: u32,
+        b: u64,
+        c: i64,
+        d: f64,
+    }
+
+    let data = Data {
+        a: 1,
+        b: u64::MAX,
+        c: -2,
+        d: 0.5,
+    };
+    assert!(serialize_webgpu(&data).is_err());
+    assert!(serialize_webgpu_with(&data, &config(true, false)).is_err());
+
+    let buffer = serialize_webgpu_with(&data, &config(true, true)).unwrap();
+    assert_eq!(buffer.len(), 32);
+    assert_eq!(buffer[8..16], [0xFF; 8]);
+    assert_eq!(buffer[16..24], (-2i64).to_le_bytes());
+    assert_eq!(buffer[24..32], 0.5f64.to_le_bytes());
+}
+
+#[test]
+fn vector() {
+    #[derive(Serialize)]
+    struct Data {
+        a: f64,
+        b: vec3<f64>,
+        c: vec2<u64>,
+        d: mat3x3<f64>,
+    }
+
+    let data = Data {
+        a: 1.0,
+        b: vec3([2.0; 3]),
+        c: vec2([3; 2]),
+        d: mat3x3([vec3([4.0; 3]); 3]),
+    };
+    let buffer = serialize_webgpu_with(&data, &config(true, true)).unwrap();
+    // b at 32, c at 64, d at 96 with a column stride of 32.
+    assert_eq!(buffer.len(), 192);
+    assert_eq!(buffer[32..40], 2.0f64.to_le_bytes());
+    assert_eq!(buffer[56..64], [0; 8]);
+    assert_eq!(buffer[64..72], 3u64.to_le_bytes());
+    assert_eq!(buffer[128..136], 4.0f64.to_le_bytes());
+}
@@ -0,0 +1,90 @@
+use serde::Serialize;
+
+use serde_webgpu::atomic::atomic;
+use serde_webgpu::f16;
+use serde_webgpu::layout::{verify, verify_with, WgslField, WgslLayout};
+use serde_webgpu::mat::{mat2x3, mat3x2, mat3x3, mat4x4};
+use serde_webgpu::string::WgslString;
+use serde_webgpu::vec::{vec2, vec3, vec4};
+use serde_webgpu::{AddressSpace, ErrorKind, WebGPUConfig};
+
+#[derive(Serialize, Default)]
+struct Instance {
+    model: mat4x4<f32>,
+    color: vec3<f32>,
+    id: u32,
+    offsets: [vec2<f32>; 3],
+}
+
+impl WgslLayout for Instance {
+    const SIZE: usize = 112;
+    const ALIGN: usize = 16;
+    const WGSL_TYPE_NAME: &'static str = "Instance";
+    const FIELDS: &'static [WgslField] = &[
+        WgslField::new::<mat4x4<f32>>("model", 0),
+        WgslField::new::<vec3<f32>>("color", 64),
+        WgslField::new::<u32>("id", 76),
+        WgslField::new::<[vec2<f32>; 3]>("offsets", 80),
+    ];
+}
+
+#[test]
+fn constants() {
+    assert_eq!((vec3::<f32>::SIZE, vec3::<f32>::ALIGN), (12, 16));
+    assert_eq!((vec2::<f16>::SIZE, vec2::<f16>::ALIGN), (4, 4));
+    assert_eq!((vec4::<f64>::SIZE, vec4::<f64>::ALIGN), (32, 32));
+    assert_eq!((mat3x3::<f32>::SIZE, mat3x3::<f32>::ALIGN), (48, 16));
+    assert_eq!((mat3x2::<f16>::SIZE, mat3x2::<f16>::ALIGN), (12, 4));
+    assert_eq!(<[vec3<f32>; 4]>::SIZE, 64);
+    assert_eq!(<[vec3<f32>; 4]>::wgsl_type(), "array<vec3<f32>, 4>");
+    assert_eq!(vec3::<bool>::wgsl_type(), "vec3<u32>");
+    assert_eq!(mat2x3::<f32>::wgsl_type(), "mat2x3<f32>");
+    assert_eq!(WgslString::<10>::wgsl_type(), "WgslString10");
+    assert_eq!((Instance::FIELDS[3].wgsl_type)(), "array<vec2<f32>, 3>");
+}
+
+#[test]
+fn verify_runtime() {
+    verify(&Instance::default()).unwrap();
+    verify(&[Instance::default(), Instance::default()]).unwrap();
+    verify(&mat3x2::<f16>::default()).unwrap();
+    verify(&mat2x3::<f64>::default()).unwrap();
+    verify(&[vec3([1u64; 3]); 2]).unwrap();
+    verify(&WgslString::<5>::new("hello").unwrap()).unwrap();
+
+    let storage = WebGPUConfig {
+        address_space: AddressSpace::Storage,
+        ..Default::default()
+    };
+    verify_with(&[atomic(1u32), atomic(2)], &storage).unwrap();
+}
+
+#[test]
+fn vectors() {
+    // The serializer and the static layout agree for every scalar.
+    macro_rules! check {
+        ($($t:ty),*) => {$(
+            verify(&vec2::<$t>::default()).unwrap();
+            verify(&vec3::<$t>::default()).unwrap();
+            verify(&vec4::<$t>::default()).unwrap();
+        )*};
+    }
+    check!(bool, f16, i32, u32, f32, i64, u64, f64);
+}
+
+#[test]
+fn mismatch() {
+    #[derive(Serialize)]
+    struct Wrong {
+        a: vec3<f32>,
+    }
+
+    impl WgslLayout for Wrong {
+        const SIZE: usize = 12;
+        const ALIGN: usize = 16;
+        const WGSL_TYPE_NAME: &'static str = "Wrong";
+    }
+
+    let error = verify(&Wrong { a: vec3([0.0; 3]) }).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::Size);
+}
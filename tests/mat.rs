@@ -0,0 +1,80 @@
+use serde_webgpu::f16;
+use serde_webgpu::mat::{mat2x2, mat2x3, mat3x2, mat3x3, mat4x4};
+use serde_webgpu::serialize_webgpu;
+use serde_webgpu::vec::{vec2, vec3, vec4};
+
+#[test]
+fn layout() {
+    let m = mat2x3::from([[1.0f32, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+    let bytes = serialize_webgpu(&m).unwrap();
+    assert_eq!(bytes.len(), 32);
+    assert_eq!(&bytes[16..20], &4.0f32.to_le_bytes());
+
+    let m = mat3x2::<f16>::default();
+    assert_eq!(serialize_webgpu(&m).unwrap().len(), 12);
+
+    assert_eq!(
+        serialize_webgpu(&mat4x4::<f32>::identity()).unwrap().len(),
+        64
+    );
+}
+
+#[test]
+fn construct() {
+    let m = mat3x2::from_rows([vec3([1.0f32, 2.0, 3.0]), vec3([4.0, 5.0, 6.0])]);
+    assert_eq!(m.col(1).0, [2.0, 5.0]);
+    assert_eq!(m.row(1).0, [4.0, 5.0, 6.0]);
+    assert_eq!(
+        m.transpose().0.map(|c| c.0),
+        [[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]
+    );
+    assert_eq!(
+        <[[f32; 2]; 3]>::from(m),
+        [[1.0, 4.0], [2.0, 5.0], [3.0, 6.0]]
+    );
+}
+
+#[test]
+fn multiply() {
+    let m = mat2x2::from_rows([vec2([1.0f32, 2.0]), vec2([3.0, 4.0])]);
+    assert_eq!((m * vec2([1.0, 1.0])).0, [3.0, 7.0]);
+    assert_eq!((m * m).row(0).0, [7.0, 10.0]);
+
+    let a = mat3x2::from_rows([vec3([1.0f32, 0.0, 2.0]), vec3([0.0, 1.0, 0.0])]);
+    let b: mat2x3<f32> = a.transpose();
+    let c: mat2x2<f32> = a * b;
+    assert_eq!(<[[f32; 2]; 2]>::from(c), [[5.0, 0.0], [0.0, 1.0]]);
+
+    let translate = mat4x4::from_rows([
+        vec4([1.0f32, 0.0, 0.0, 3.0]),
+        vec4([0.0, 1.0, 0.0, 4.0]),
+        vec4([0.0, 0.0, 1.0, 5.0]),
+        vec4([0.0, 0.0, 0.0, 1.0]),
+    ]);
+    assert_eq!(
+        (translate * vec4([1.0, 1.0, 1.0, 1.0])).0,
+        [4.0, 5.0, 6.0, 1.0]
+    );
+}
+
+#[test]
+fn inverse() {
+    let m = mat3x3::from_rows([
+        vec3([2.0f64, 0.0, 1.0]),
+        vec3([1.0, 3.0, 0.0]),
+        vec3([0.0, 1.0, 4.0]),
+    ]);
+    assert!((m.determinant() - 25.0).abs() < 1e-12);
+
+    let product = m * m.inverse().unwrap();
+    for c in 0..3 {
+        for r in 0..3 {
+            let expected = if c == r { 1.0 } else { 0.0 };
+            assert!((product[c][r] - expected).abs() < 1e-12);
+        }
+    }
+
+    let singular = mat2x2::from([[1.0f32, 2.0], [2.0, 4.0]]);
+    assert_eq!(singular.determinant(), 0.0);
+    assert!(singular.inverse().is_none());
+}
@@ -0,0 +1,71 @@
+use serde::Serialize;
+
+use serde_webgpu::mesh::{build_mesh, IndexBuffer, MeshBuilder};
+use serde_webgpu::vec::{vec2, vec3};
+
+#[derive(Serialize)]
+struct Vertex {
+    position: vec3<f32>,
+    uv: vec2<f32>,
+}
+
+#[test]
+fn weld() {
+    let quad = [
+        [0.0, 0.0],
+        [1.0, 0.0],
+        [1.0, 1.0],
+        [0.0, 0.0],
+        [1.0, 1.0],
+        [0.0, 1.0],
+    ];
+    let mesh = build_mesh(quad.iter().map(|&[x, y]| Vertex {
+        position: vec3([x, y, 0.0]),
+        uv: vec2([x, y]),
+    }))
+    .unwrap();
+
+    assert_eq!(mesh.vertex_stride, 32);
+    assert_eq!(mesh.vertex_count, 4);
+    assert_eq!(mesh.vertices.len(), 4 * 32);
+    assert_eq!(mesh.indices, IndexBuffer::Uint16(vec![0, 1, 2, 0, 2, 3]));
+    assert_eq!(
+        mesh.indices.to_bytes(),
+        [0, 0, 1, 0, 2, 0, 0, 0, 2, 0, 3, 0]
+    );
+    assert!(!mesh.primitive_restart);
+}
+
+#[test]
+fn index_format() {
+    let mesh = build_mesh(0..0x10000u32).unwrap();
+    assert!(matches!(mesh.indices, IndexBuffer::Uint16(_)));
+
+    let mut builder = MeshBuilder::new();
+    builder.extend(0..0x10000u32).unwrap();
+    builder.restart();
+    let mesh = builder.build().unwrap();
+    assert!(mesh.primitive_restart);
+    match mesh.indices {
+        IndexBuffer::Uint32(i) => assert_eq!(i[0x10000], u32::MAX),
+        IndexBuffer::Uint16(_) => panic!("0x10000 vertices need restart value 0xFFFF"),
+    }
+
+    let mut builder = MeshBuilder::new();
+    builder.extend([1u32, 2, 3]).unwrap();
+    builder.restart();
+    builder.extend([3u32, 4]).unwrap();
+    let mesh = builder.build().unwrap();
+    assert_eq!(
+        mesh.indices,
+        IndexBuffer::Uint16(vec![0, 1, 2, 0xFFFF, 2, 3])
+    );
+    assert_eq!(mesh.indices.to_bytes().len(), 12);
+}
+
+#[test]
+fn stride_mismatch() {
+    let mut builder = MeshBuilder::new();
+    builder.push(&1.0f32).unwrap();
+    assert!(builder.push(&vec2([1.0f32, 2.0])).is_err());
+}
@@ -0,0 +1,73 @@
+use half::f16;
+use serde::Serialize;
+
+use serde_webgpu::layout::{verify, WgslLayout};
+use serde_webgpu::mat::mat3x3;
+use serde_webgpu::packed::{packed_vec3, packed_vec4};
+use serde_webgpu::vec::{vec2, vec3};
+use serde_webgpu::{serialize_webgpu, serialize_webgpu_with, LayoutMode, WebGPUConfig};
+
+fn msl() -> WebGPUConfig {
+    WebGPUConfig {
+        layout: LayoutMode::Msl,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn vectors() {
+    #[derive(Serialize)]
+    struct Light {
+        position: vec3<f32>,
+        intensity: f32,
+        color: vec3<f16>,
+        range: f16,
+        size: vec2<f32>,
+    }
+
+    let light = Light {
+        position: vec3([1.0; 3]),
+        intensity: 2.0,
+        color: vec3([f16::ONE; 3]),
+        range: f16::ONE,
+        size: vec2([3.0; 2]),
+    };
+    // `intensity` and `range` fill the tail of the vectors in WGSL, but not in MSL.
+    assert_eq!(serialize_webgpu(&light).unwrap().len(), 32);
+    let bytes = serialize_webgpu_with(&light, &msl()).unwrap();
+    assert_eq!(bytes.len(), 48);
+    assert_eq!(bytes[16..20], 2.0f32.to_le_bytes());
+    assert_eq!(bytes[32..34], f16::ONE.to_le_bytes());
+    assert_eq!(bytes[40..44], 3.0f32.to_le_bytes());
+
+    // Matrix columns are 16 bytes either way.
+    let basis = mat3x3::<f32>::identity();
+    assert_eq!(
+        serialize_webgpu_with(&basis, &msl()).unwrap(),
+        serialize_webgpu(&basis).unwrap()
+    );
+}
+
+#[test]
+fn packed() {
+    #[derive(Serialize)]
+    struct Vertex {
+        weight: f32,
+        position: packed_vec3<f32>,
+        color: packed_vec4<f16>,
+    }
+
+    let vertex = Vertex {
+        weight: 1.0,
+        position: packed_vec3([2.0; 3]),
+        color: packed_vec4([f16::ONE; 4]),
+    };
+    let bytes = serialize_webgpu_with(&vertex, &msl()).unwrap();
+    assert_eq!(bytes.len(), 24);
+    assert_eq!(bytes[4..8], 2.0f32.to_le_bytes());
+    assert_eq!(bytes[16..18], f16::ONE.to_le_bytes());
+    assert_eq!(serialize_webgpu(&vertex).unwrap(), bytes);
+
+    assert_eq!(packed_vec3::<f32>::wgsl_type(), "array<f32, 3>");
+    verify(&packed_vec3([1.0f32; 3])).unwrap();
+}
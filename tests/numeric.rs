@@ -0,0 +1,115 @@
+use half::f16;
+use serde::Serialize;
+
+use serde_webgpu::{
+    serialize_webgpu, serialize_webgpu_with, ErrorKind, F64Policy, NumericPolicy, WebGPUConfig,
+};
+
+fn config(numeric: NumericPolicy) -> WebGPUConfig {
+    WebGPUConfig {
+        numeric,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn narrow() {
+    #[derive(Serialize)]
+    struct Draw {
+        index: usize,
+        offset: i64,
+    }
+
+    let narrow = config(NumericPolicy {
+        narrow_int64: true,
+        ..Default::default()
+    });
+    let draw = Draw {
+        index: 3,
+        offset: -1,
+    };
+    assert!(serialize_webgpu(&draw).is_err());
+    assert_eq!(
+        serialize_webgpu_with(&draw, &narrow).unwrap(),
+        [3, 0, 0, 0, 0xFF, 0xFF, 0xFF, 0xFF]
+    );
+
+    let draw = Draw {
+        index: u32::MAX as usize + 1,
+        offset: 0,
+    };
+    let error = serialize_webgpu_with(&draw, &narrow).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::Overflow);
+    assert_eq!(error.message(), "u64 value 4294967296 overflows u32");
+}
+
+#[test]
+fn f64() {
+    let lossy = config(NumericPolicy {
+        f64: F64Policy::Lossy,
+        ..Default::default()
+    });
+    let checked = config(NumericPolicy {
+        f64: F64Policy::Checked,
+        ..Default::default()
+    });
+
+    assert!(serialize_webgpu(&0.1f64).is_err());
+    assert_eq!(
+        serialize_webgpu_with(&0.1f64, &lossy).unwrap(),
+        0.1f32.to_le_bytes()
+    );
+    assert_eq!(
+        serialize_webgpu_with(&0.1f64, &checked).unwrap(),
+        0.1f32.to_le_bytes()
+    );
+    assert!(serialize_webgpu_with(&f64::NAN, &checked).is_ok());
+    assert_eq!(
+        serialize_webgpu_with(&1e300f64, &lossy).unwrap(),
+        f32::INFINITY.to_le_bytes()
+    );
+    assert!(serialize_webgpu_with(&f64::INFINITY, &checked).is_ok());
+
+    #[derive(Serialize)]
+    struct Far {
+        near: f64,
+        far: f64,
+    }
+    let far = Far {
+        near: 0.1,
+        far: 1e300,
+    };
+    let error = serialize_webgpu_with(&far, &checked).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::Overflow);
+    assert_eq!(error.path(), Some("far"));
+    assert_eq!(error.message(), "f64 value 1e300 overflows f32");
+}
+
+#[test]
+fn widen() {
+    #[derive(Serialize)]
+    struct Small {
+        a: u8,
+        b: i8,
+        c: u16,
+        d: i16,
+        e: f16,
+    }
+
+    let widen = config(NumericPolicy {
+        widen_small_ints: true,
+        ..Default::default()
+    });
+    let small = Small {
+        a: 1,
+        b: -1,
+        c: 2,
+        d: -2,
+        e: f16::ONE,
+    };
+    let buffer = serialize_webgpu_with(&small, &widen).unwrap();
+    assert_eq!(buffer.len(), 20);
+    assert_eq!(buffer[4..8], (-1i32).to_le_bytes());
+    assert_eq!(buffer[12..16], (-2i32).to_le_bytes());
+    assert_eq!(buffer[16..18], f16::ONE.to_le_bytes());
+}
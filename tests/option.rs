@@ -0,0 +1,78 @@
+use serde::{Serialize, Serializer};
+
+use serde_webgpu::option::{sentinel_with, OptionFlag, OptionSentinel, OptionZeroed};
+use serde_webgpu::serialize_webgpu;
+use serde_webgpu::vec::vec3;
+
+#[test]
+fn flag() {
+    let some = serialize_webgpu(&OptionFlag(Some(vec3([1.0f32, 2.0, 3.0])))).unwrap();
+    let none = serialize_webgpu(&OptionFlag::<vec3<f32>>(None)).unwrap();
+    assert_eq!(some.len(), 32);
+    assert_eq!(none.len(), 32);
+    assert_eq!(some[..4], [1, 0, 0, 0]);
+    assert_eq!(some[16..20], 1.0f32.to_le_bytes());
+    assert_eq!(none, [0; 32]);
+}
+
+#[test]
+fn sentinel() {
+    #[derive(Serialize)]
+    struct Pick {
+        object: OptionSentinel<u32>,
+        #[serde(serialize_with = "zero_sentinel")]
+        layer: Option<u32>,
+    }
+
+    fn zero_sentinel<S: Serializer>(value: &Option<u32>, serializer: S) -> Result<S::Ok, S::Error> {
+        sentinel_with(value, &0, serializer)
+    }
+
+    let pick = Pick {
+        object: OptionSentinel(None),
+        layer: None,
+    };
+    assert_eq!(
+        serialize_webgpu(&pick).unwrap(),
+        [0xFF, 0xFF, 0xFF, 0xFF, 0, 0, 0, 0]
+    );
+
+    let pick = Pick {
+        object: OptionSentinel(Some(3)),
+        layer: Some(2),
+    };
+    assert_eq!(serialize_webgpu(&pick).unwrap(), [3, 0, 0, 0, 2, 0, 0, 0]);
+}
+
+#[test]
+fn zeroed() {
+    #[derive(Serialize)]
+    struct Light {
+        a: f32,
+        b: OptionZeroed<vec3<f32>>,
+        c: f32,
+    }
+
+    let some = Light {
+        a: 1.0,
+        b: OptionZeroed(Some(vec3([2.0; 3]))),
+        c: 4.0,
+    };
+    let none = Light {
+        a: 1.0,
+        b: OptionZeroed(None),
+        c: 4.0,
+    };
+    let some = serialize_webgpu(&some).unwrap();
+    let none = serialize_webgpu(&none).unwrap();
+    assert_eq!(some.len(), 32);
+    assert_eq!(none.len(), some.len());
+    assert_eq!(some[16..20], 2.0f32.to_le_bytes());
+    assert_eq!(some[28..32], 4.0f32.to_le_bytes());
+    assert_eq!(none[..4], 1.0f32.to_le_bytes());
+    assert!(none[4..28].iter().all(|&i| i == 0));
+    assert_eq!(none[28..32], 4.0f32.to_le_bytes());
+
+    assert!(serialize_webgpu(&Some(1.0f32)).is_err());
+    assert!(serialize_webgpu(&None::<f32>).is_err());
+}
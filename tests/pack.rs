@@ -0,0 +1,49 @@
+use serde_webgpu::pack::*;
+use serde_webgpu::serialize_webgpu;
+use serde_webgpu::vec::{vec2, vec4};
+
+#[test]
+fn norm() {
+    // floor(0.5 + 127 * e), with 0.5 rounding up to 1 and -0.5 rounding up to 0.
+    assert_eq!(
+        pack4x8snorm(vec4([1.0, -1.0, 2.0 / 254.0 - 1.0 / 254.0, -3.0])),
+        0x81_01_81_7F
+    );
+    assert_eq!(pack4x8snorm(vec4([-1.0 / 254.0, 0.0, 0.0, 0.0])), 0);
+    assert_eq!(unpack4x8snorm(0x80_00_81_7F), vec4([1.0, -1.0, 0.0, -1.0]));
+    assert_eq!(
+        pack4x8unorm(vec4([0.5, 2.0, -1.0, f32::NAN])),
+        0x00_00_FF_80
+    );
+    assert_eq!(pack2x16snorm(vec2([-0.5, 0.25])), 0x2000_C001);
+    assert_eq!(
+        unpack2x16unorm(pack2x16unorm(vec2([1.0, 0.0]))),
+        vec2([1.0, 0.0])
+    );
+}
+
+#[test]
+fn float() {
+    assert_eq!(pack2x16float(vec2([1.0, -2.0])), 0xC000_3C00);
+    assert_eq!(unpack2x16float(0xC000_3C00), vec2([1.0, -2.0]));
+    assert_eq!(Float2x16::new(vec2([65536.0, 0.0])).get()[0], f32::INFINITY);
+}
+
+#[test]
+fn int() {
+    assert_eq!(pack4xi8(vec4([-1, 127, 256, -129])), 0x7F_00_7F_FF);
+    assert_eq!(pack4xi8_clamp(vec4([-1, 127, 256, -129])), 0x80_7F_7F_FF);
+    assert_eq!(unpack4xi8(0x80_7F_7F_FF), vec4([-1, 127, 127, -128]));
+    assert_eq!(pack4xu8(vec4([1, 2, 256, 300])), 0x2C_00_02_01);
+    assert_eq!(pack4xu8_clamp(vec4([1, 2, 256, 300])), 0xFF_FF_02_01);
+    assert_eq!(U8x4::from(vec4([4, 3, 2, 1])).get(), vec4([4, 3, 2, 1]));
+}
+
+#[test]
+fn serialize() {
+    let packed = [I8x4::new(vec4([-1, 0, 1, 2])), I8x4::default()];
+    assert_eq!(
+        serialize_webgpu(&packed).unwrap(),
+        [0xFF, 0, 1, 2, 0, 0, 0, 0]
+    );
+}
@@ -0,0 +1,70 @@
+use serde_webgpu::layout::{WgslField, WgslLayout};
+use serde_webgpu::mat::mat4x4;
+use serde_webgpu::padding::analyze_padding;
+use serde_webgpu::vec::{vec2, vec3, vec4};
+
+struct Material;
+
+impl WgslLayout for Material {
+    const SIZE: usize = 48;
+    const ALIGN: usize = 16;
+    const WGSL_TYPE_NAME: &'static str = "Material";
+    const FIELDS: &'static [WgslField] = &[
+        WgslField::new::<f32>("roughness", 0),
+        WgslField::new::<vec4<f32>>("albedo", 16),
+        WgslField::new::<f32>("metallic", 32),
+    ];
+}
+
+struct Object;
+
+impl WgslLayout for Object {
+    const SIZE: usize = 144;
+    const ALIGN: usize = 16;
+    const WGSL_TYPE_NAME: &'static str = "Object";
+    const FIELDS: &'static [WgslField] = &[
+        WgslField::new::<mat4x4<f32>>("model", 0),
+        WgslField::new::<vec3<f32>>("center", 64),
+        WgslField::new::<f32>("radius", 76),
+        WgslField::new::<Material>("material", 80),
+        WgslField::new::<vec2<f32>>("uv_scale", 128),
+        WgslField::new::<u32>("id", 136),
+    ];
+}
+
+#[test]
+fn nested() {
+    let report = analyze_padding::<Object>();
+    // `Object` is already as small as it gets, but `Material` is not.
+    assert_eq!((report.padding(), report.savings()), (4, 0));
+
+    let material = &report.nested[0];
+    assert_eq!(material.type_name, "Material");
+    assert_eq!((material.padding(), material.savings()), (24, 16));
+    let suggested: Vec<_> = material
+        .suggested
+        .iter()
+        .map(|i| (i.name, i.offset))
+        .collect();
+    assert_eq!(
+        suggested,
+        [("albedo", 0), ("roughness", 16), ("metallic", 20)]
+    );
+    assert_eq!(report.total_savings(), 16);
+    let lines: Vec<_> = report.to_string().lines().map(str::to_string).collect();
+    assert_eq!(
+        lines,
+        [
+            "Object: 144 bytes with 4 bytes of padding",
+            "",
+            "Material: 48 bytes with 24 bytes of padding",
+            "reordered: 32 bytes with 8 bytes of padding",
+            "  struct Material {",
+            "-     roughness: f32,",
+            "      albedo: vec4<f32>,",
+            "+     roughness: f32,",
+            "      metallic: f32,",
+            "  }",
+        ]
+    );
+}
@@ -0,0 +1,77 @@
+use serde::Serialize;
+
+use serde_webgpu::mat::{mat2x2, mat3x3};
+use serde_webgpu::vec::{vec2, vec3};
+use serde_webgpu::{serialize_webgpu, serialize_webgpu_with, LayoutMode, WebGPUConfig};
+
+#[derive(Serialize)]
+struct Inner {
+    x: f32,
+}
+
+#[derive(Serialize)]
+struct Block {
+    a: f32,
+    b: [f32; 3],
+    c: vec2<f32>,
+    d: mat2x2<f32>,
+    e: Inner,
+    f: f32,
+}
+
+fn std140() -> WebGPUConfig {
+    WebGPUConfig {
+        layout: LayoutMode::Std140,
+        ..Default::default()
+    }
+}
+
+fn f32_at(bytes: &[u8], offset: usize) -> f32 {
+    f32::from_le_bytes(bytes[offset..offset + 4].try_into().unwrap())
+}
+
+#[test]
+fn block() {
+    let block = Block {
+        a: 1.0,
+        b: [2.0, 3.0, 4.0],
+        c: vec2([5.0, 6.0]),
+        d: mat2x2::from([[7.0, 8.0], [9.0, 10.0]]),
+        e: Inner { x: 11.0 },
+        f: 12.0,
+    };
+    assert_eq!(serialize_webgpu(&block).unwrap().len(), 48);
+
+    let bytes = serialize_webgpu_with(&block, &std140()).unwrap();
+    assert_eq!(bytes.len(), 144);
+    let offsets = [0, 16, 32, 48, 64, 68, 80, 84, 96, 100, 112, 128];
+    for (i, offset) in offsets.into_iter().enumerate() {
+        assert_eq!(f32_at(&bytes, offset), (i + 1) as f32, "offset {}", offset);
+    }
+}
+
+#[test]
+fn packing() {
+    #[derive(Serialize)]
+    struct Packed {
+        position: vec3<f32>,
+        radius: f32,
+        normal: mat3x3<f32>,
+    }
+
+    let packed = Packed {
+        position: vec3([1.0; 3]),
+        radius: 2.0,
+        normal: mat3x3::identity(),
+    };
+    let bytes = serialize_webgpu_with(&packed, &std140()).unwrap();
+    assert_eq!(bytes.len(), 64);
+    assert_eq!(f32_at(&bytes, 12), 2.0);
+    assert_eq!(f32_at(&bytes, 36), 1.0);
+    assert_eq!(
+        serialize_webgpu_with(&[Inner { x: 1.0 }, Inner { x: 2.0 }], &std140())
+            .unwrap()
+            .len(),
+        32
+    );
+}
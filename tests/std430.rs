@@ -0,0 +1,53 @@
+use serde::Serialize;
+
+use serde_webgpu::mat::mat3x3;
+use serde_webgpu::vec::{vec2, vec3};
+use serde_webgpu::{serialize_webgpu, serialize_webgpu_with, LayoutMode, WebGPUConfig};
+
+#[derive(Serialize)]
+struct Particle {
+    position: vec3<f32>,
+    velocity: vec3<f32>,
+    uv: vec2<f32>,
+    weights: [f32; 3],
+    basis: mat3x3<f32>,
+}
+
+fn config(layout: LayoutMode) -> WebGPUConfig {
+    WebGPUConfig {
+        layout,
+        ..Default::default()
+    }
+}
+
+fn particle() -> Particle {
+    Particle {
+        position: vec3([1.0; 3]),
+        velocity: vec3([2.0; 3]),
+        uv: vec2([3.0; 2]),
+        weights: [4.0; 3],
+        basis: mat3x3::identity(),
+    }
+}
+
+#[test]
+fn std430() {
+    let bytes = serialize_webgpu_with(&particle(), &config(LayoutMode::Std430)).unwrap();
+    assert_eq!(bytes, serialize_webgpu(&particle()).unwrap());
+    assert_eq!(bytes.len(), 112);
+}
+
+#[test]
+fn scalar() {
+    let bytes = serialize_webgpu_with(&particle(), &config(LayoutMode::Scalar)).unwrap();
+    // Everything is tightly packed on 4 bytes.
+    assert_eq!(bytes.len(), 4 * (3 + 3 + 2 + 3 + 9));
+    assert_eq!(bytes[12..16], 2.0f32.to_le_bytes());
+    assert_eq!(bytes[24..28], 3.0f32.to_le_bytes());
+    assert_eq!(bytes[32..36], 4.0f32.to_le_bytes());
+
+    let array = [vec3([1.0f64; 3]), vec3([2.0; 3])];
+    let mut config = config(LayoutMode::Scalar);
+    config.extensions.f64 = true;
+    assert_eq!(serialize_webgpu_with(&array, &config).unwrap().len(), 48);
+}
@@ -0,0 +1,69 @@
+use serde::Serialize;
+
+use serde_webgpu::layout::WgslLayout;
+use serde_webgpu::string::WgslString;
+use serde_webgpu::{serialize_webgpu, serialize_webgpu_with, LayoutMode, WebGPUConfig};
+
+#[test]
+fn layout() {
+    #[derive(Serialize)]
+    struct Label {
+        color: u32,
+        text: WgslString<6>,
+    }
+
+    let label = Label {
+        color: 0xFF,
+        text: WgslString::new("añb").unwrap(),
+    };
+    let buffer = serialize_webgpu(&label).unwrap();
+    assert_eq!(buffer.len(), 48);
+    assert_eq!(buffer[16..20], [4, 0, 0, 0]);
+    assert_eq!(buffer[32..40], [b'a', 0xC3, 0xB1, b'b', 0, 0, 0, 0]);
+}
+
+#[test]
+fn uniform_layouts() {
+    let text = WgslString::<20>::new("abcdefghijklmnopqr").unwrap();
+    let decl = WgslString::<20>::wgsl_decl();
+    assert!(decl.contains("    data: array<vec4<u32>, 2>,\n"));
+    for layout in [
+        LayoutMode::Wgsl,
+        LayoutMode::Std140,
+        LayoutMode::HlslCbuffer,
+    ] {
+        let config = WebGPUConfig {
+            layout,
+            ..Default::default()
+        };
+        let buffer = serialize_webgpu_with(&text, &config).unwrap();
+        assert_eq!(buffer.len(), WgslString::<20>::SIZE, "{:?}", layout);
+        assert_eq!(buffer[..4], [18, 0, 0, 0], "{:?}", layout);
+        assert_eq!(buffer[16..20], *b"abcd", "{:?}", layout);
+        // Byte 16 in the first word of `data[1]`, whose stride is 16 bytes.
+        assert_eq!(buffer[32..36], *b"qr\0\0", "{:?}", layout);
+    }
+}
+
+#[test]
+fn truncate() {
+    let s = WgslString::<3>::truncate("aññ");
+    assert_eq!(s.as_str(), "añ");
+    assert_eq!(&*WgslString::<2>::truncate("añ"), "a");
+    assert!(WgslString::<3>::new("aññ").is_err());
+}
+
+#[test]
+fn plain_str() {
+    assert!(serialize_webgpu(&"text").is_err());
+    assert!(serialize_webgpu(&String::from("text")).is_err());
+}
+
+#[test]
+fn wgsl() {
+    let decl = WgslString::<10>::wgsl_decl();
+    assert!(
+        decl.contains("struct WgslString10 {\n    len: u32,\n    data: array<vec4<u32>, 1>,\n}")
+    );
+    assert!(decl.contains("fn wgsl_string_byte(chunk: vec4<u32>, i: u32) -> u32"));
+}
@@ -0,0 +1,239 @@
+use serde::Serialize;
+
+use serde_webgpu::tagged::{Tagged, WgslUnion};
+use serde_webgpu::vec::{vec2, vec4};
+use serde_webgpu::{serialize_webgpu, ErrorKind};
+
+#[derive(Serialize)]
+enum Material {
+    Unlit(vec4<f32>),
+    Lambert { albedo: vec4<f32>, wrap: f32 },
+    Mirror,
+}
+
+impl WgslUnion for Material {
+    fn variants() -> Vec<Self> {
+        vec![
+            Material::Unlit(Default::default()),
+            Material::Lambert {
+                albedo: Default::default(),
+                wrap: 0.0,
+            },
+            Material::Mirror,
+        ]
+    }
+}
+
+#[derive(Serialize)]
+struct Object {
+    id: u32,
+    #[serde(serialize_with = "serde_webgpu::tagged::serialize")]
+    material: Material,
+    roughness: f32,
+}
+
+#[test]
+fn external() {
+    let object = Object {
+        id: 7,
+        material: Material::Unlit(vec4([1.0, 0.0, 0.0, 1.0])),
+        roughness: 0.5,
+    };
+    let buffer = serialize_webgpu(&object).unwrap();
+
+    // id, tag at 16, payload at 32..64, roughness at 64.
+    assert_eq!(buffer.len(), 80);
+    assert_eq!(buffer[16..20], [0, 0, 0, 0]);
+    assert_eq!(buffer[32..36], 1.0f32.to_le_bytes());
+    assert!(buffer[48..64].iter().all(|&i| i == 0));
+    assert_eq!(buffer[64..68], 0.5f32.to_le_bytes());
+
+    let object = Object {
+        material: Material::Mirror,
+        ..object
+    };
+    let buffer = serialize_webgpu(&object).unwrap();
+    assert_eq!(buffer.len(), 80);
+    assert_eq!(buffer[16..20], [2, 0, 0, 0]);
+}
+
+#[test]
+fn json() {
+    let object = Object {
+        id: 7,
+        material: Material::Lambert {
+            albedo: vec4([1.0, 0.0, 0.0, 1.0]),
+            wrap: 0.5,
+        },
+        roughness: 0.5,
+    };
+    let json = serde_json::to_string(&object).unwrap();
+    assert_eq!(
+        json,
+        r#"{"id":7,"material":{"Lambert":{"albedo":[1.0,0.0,0.0,1.0],"wrap":0.5}},"roughness":0.5}"#
+    );
+    assert!(json.contains(&serde_json::to_string(&object.material).unwrap()));
+    assert_eq!(
+        serde_json::to_string(&Tagged(Material::Mirror)).unwrap(),
+        r#""Mirror""#
+    );
+
+    // Still a union for the WebGPU serializer afterwards.
+    assert_eq!(serialize_webgpu(&object).unwrap().len(), 80);
+}
+
+#[test]
+fn adjacent() {
+    #[derive(Serialize)]
+    #[serde(tag = "kind", content = "value")]
+    enum Adjacent {
+        A(f32),
+        B(u32),
+    }
+
+    impl WgslUnion for Adjacent {
+        const VARIANTS: &'static [&'static str] = &["A", "B"];
+
+        fn variants() -> Vec<Self> {
+            vec![Adjacent::A(0.0), Adjacent::B(0)]
+        }
+    }
+
+    let error = serialize_webgpu(&Tagged(Adjacent::A(1.0))).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::Union);
+    assert!(error.message().starts_with("adjacently tagged enum"));
+}
+
+#[test]
+fn internal() {
+    #[derive(Serialize)]
+    #[serde(tag = "kind")]
+    enum Light {
+        Point { position: vec2<f32> },
+        Spot { position: vec2<f32>, angle: f32 },
+    }
+
+    impl WgslUnion for Light {
+        const VARIANTS: &'static [&'static str] = &["Point", "Spot"];
+
+        fn variants() -> Vec<Self> {
+            vec![
+                Light::Point {
+                    position: Default::default(),
+                },
+                Light::Spot {
+                    position: Default::default(),
+                    angle: 0.0,
+                },
+            ]
+        }
+    }
+
+    let light = Light::Spot {
+        position: vec2([1.0, 2.0]),
+        angle: 3.0,
+    };
+    let buffer = serialize_webgpu(&Tagged(light)).unwrap();
+    assert_eq!(buffer.len(), 24);
+    assert_eq!(buffer[..4], [1, 0, 0, 0]);
+    assert_eq!(buffer[16..20], 3.0f32.to_le_bytes());
+
+    let light = Light::Point {
+        position: vec2([1.0, 2.0]),
+    };
+    let buffer = serialize_webgpu(&Tagged(light)).unwrap();
+    assert_eq!(buffer.len(), 24);
+    assert_eq!(buffer[..4], [0, 0, 0, 0]);
+    assert_eq!(buffer[16..24], [0; 8]);
+}
+
+#[test]
+fn representations() {
+    macro_rules! shape {
+        ($name:ident $(#[$attr:meta])*) => {
+            #[derive(Serialize)]
+            $(#[$attr])*
+            enum $name {
+                Circle { radius: f32 },
+                Rect { size: vec2<f32> },
+                Empty {},
+            }
+
+            impl WgslUnion for $name {
+                const VARIANTS: &'static [&'static str] = &["Circle", "Rect", "Empty"];
+
+                // Not in declaration order, which must not change the tags.
+                fn variants() -> Vec<Self> {
+                    vec![
+                        $name::Empty {},
+                        $name::Rect {
+                            size: Default::default(),
+                        },
+                        $name::Circle { radius: 0.0 },
+                    ]
+                }
+            }
+        };
+    }
+    shape!(External);
+    shape!(Internal #[serde(tag = "kind")]);
+
+    let tag = |buffer: Vec<u8>| buffer[..4].to_vec();
+    let pairs = [
+        (
+            External::Circle { radius: 1.0 },
+            Internal::Circle { radius: 1.0 },
+        ),
+        (
+            External::Rect {
+                size: vec2([1.0, 2.0]),
+            },
+            Internal::Rect {
+                size: vec2([1.0, 2.0]),
+            },
+        ),
+        (External::Empty {}, Internal::Empty {}),
+    ];
+    for (index, (external, internal)) in pairs.into_iter().enumerate() {
+        let external = serialize_webgpu(&Tagged(external)).unwrap();
+        let internal = serialize_webgpu(&Tagged(internal)).unwrap();
+        assert_eq!(tag(external), [index as u8, 0, 0, 0]);
+        assert_eq!(tag(internal), [index as u8, 0, 0, 0]);
+    }
+}
+
+#[test]
+fn invalid() {
+    assert!(serialize_webgpu(&Material::Mirror).is_ok());
+    assert!(serialize_webgpu(&Material::Unlit(Default::default())).is_err());
+
+    #[derive(Serialize)]
+    enum Partial {
+        A(f32),
+        B(vec4<f32>),
+    }
+
+    impl WgslUnion for Partial {
+        fn variants() -> Vec<Self> {
+            vec![Partial::A(0.0)]
+        }
+    }
+
+    assert!(serialize_webgpu(&Tagged(Partial::B(Default::default()))).is_err());
+
+    #[derive(Serialize)]
+    enum Misnamed {
+        A(f32),
+        B(f32),
+    }
+
+    impl WgslUnion for Misnamed {
+        const VARIANTS: &'static [&'static str] = &["B", "A"];
+
+        fn variants() -> Vec<Self> {
+            vec![Misnamed::A(0.0), Misnamed::B(0.0)]
+        }
+    }
+
+    assert!(serialize_webgpu(&Tagged(Misnamed::A(0.0))).is_err());
+}
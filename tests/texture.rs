@@ -0,0 +1,95 @@
+use serde_webgpu::texture::{decode_texels, encode_texels, TextureFormat};
+use serde_webgpu::vec::vec4;
+
+fn round_trip(format: TextureFormat, pixel: [f32; 4]) -> [f32; 4] {
+    let bytes = encode_texels(format, &[vec4(pixel)]).unwrap();
+    assert_eq!(bytes.len(), format.texel_size());
+    decode_texels(format, &bytes).unwrap()[0].0
+}
+
+#[test]
+fn encode() {
+    let pixel = [vec4([1.0, 0.5, 0.0, 1.0])];
+    assert_eq!(
+        encode_texels(TextureFormat::Rgba8Unorm, &pixel).unwrap(),
+        [255, 128, 0, 255]
+    );
+    assert_eq!(
+        encode_texels(TextureFormat::Rgba8UnormSrgb, &pixel).unwrap(),
+        [255, 188, 0, 255]
+    );
+    assert_eq!(
+        encode_texels(TextureFormat::Bgra8UnormSrgb, &pixel).unwrap(),
+        [0, 188, 255, 255]
+    );
+    assert_eq!(
+        encode_texels(TextureFormat::R8Snorm, &[vec4([-2.0, 0.0, 0.0, 0.0])]).unwrap(),
+        [0x81]
+    );
+    assert_eq!(
+        encode_texels(TextureFormat::Rgba16Float, &pixel).unwrap(),
+        [0x00, 0x3C, 0x00, 0x38, 0x00, 0x00, 0x00, 0x3C]
+    );
+
+    let one = [vec4([1.0; 4])];
+    let packed =
+        |format| u32::from_le_bytes(encode_texels(format, &one).unwrap().try_into().unwrap());
+    assert_eq!(packed(TextureFormat::Rgb10a2Unorm), u32::MAX);
+    assert_eq!(
+        packed(TextureFormat::Rg11b10Ufloat),
+        0x3C0 | 0x3C0 << 11 | 0x1E0 << 22
+    );
+    assert_eq!(
+        packed(TextureFormat::Rgb9e5Ufloat),
+        256 | 256 << 9 | 256 << 18 | 16 << 27
+    );
+}
+
+#[test]
+fn decode() {
+    assert_eq!(
+        round_trip(TextureFormat::R32Float, [0.25, 9.0, 9.0, 9.0]),
+        [0.25, 0.0, 0.0, 1.0]
+    );
+    assert_eq!(
+        round_trip(TextureFormat::Depth32Float, [0.75, 0.0, 0.0, 0.0]),
+        [0.75, 0.0, 0.0, 1.0]
+    );
+    assert_eq!(
+        round_trip(TextureFormat::Rg32Sint, [-3.0, 7.0, 0.0, 0.0]),
+        [-3.0, 7.0, 0.0, 1.0]
+    );
+    assert_eq!(
+        round_trip(TextureFormat::Rgba16Float, [0.5, -2.0, 65504.0, 1.0]),
+        [0.5, -2.0, 65504.0, 1.0]
+    );
+    assert_eq!(
+        round_trip(TextureFormat::Rg11b10Ufloat, [0.5, 4.0, -1.0, 0.0]),
+        [0.5, 4.0, 0.0, 1.0]
+    );
+    assert_eq!(
+        round_trip(TextureFormat::Rgb9e5Ufloat, [0.5, 0.25, 3.0, 0.0]),
+        [0.5, 0.25, 3.0, 1.0]
+    );
+    assert_eq!(
+        round_trip(TextureFormat::Rgb10a2Uint, [1023.0, 0.0, 5.0, 3.0]),
+        [1023.0, 0.0, 5.0, 3.0]
+    );
+
+    let srgb = round_trip(TextureFormat::Rgba8UnormSrgb, [0.5, 0.2, 0.0, 1.0]);
+    assert!((srgb[0] - 0.5).abs() < 0.005);
+    assert!((srgb[1] - 0.2).abs() < 0.005);
+
+    assert!(decode_texels(TextureFormat::Rgba16Float, &[0; 12]).is_err());
+}
+
+#[test]
+fn compressed() {
+    assert_eq!(TextureFormat::Bc7RgbaUnormSrgb.texel_size(), 16);
+    assert_eq!(
+        TextureFormat::Astc12x10UnormSrgb.block_dimensions(),
+        (12, 10)
+    );
+    assert!(TextureFormat::Etc2Rgb8a1UnormSrgb.is_srgb());
+    assert!(encode_texels(TextureFormat::Bc1RgbaUnorm, &[vec4([0.0; 4])]).is_err());
+}
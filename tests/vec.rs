@@ -0,0 +1,110 @@
+use serde_webgpu::vec::{vec2, vec3, vec4};
+
+#[test]
+fn operators() {
+    let a = vec3([1.0f32, 2.0, 3.0]);
+    let b = vec3([4.0f32, 5.0, 6.0]);
+    assert_eq!(a + b, vec3([5.0, 7.0, 9.0]));
+    assert_eq!(b - a, vec3([3.0, 3.0, 3.0]));
+    assert_eq!(a * b, vec3([4.0, 10.0, 18.0]));
+    assert_eq!(-a, vec3([-1.0, -2.0, -3.0]));
+    assert_eq!(a * 2.0, 2.0 * a);
+    assert_eq!(12.0 / b, vec3([3.0, 2.4, 2.0]));
+
+    let mut c = vec2([7u32, 9]);
+    c += 1;
+    c /= vec2([2, 5]);
+    assert_eq!(c, vec2([4, 2]));
+}
+
+#[test]
+fn geometry() {
+    let x = vec3([1.0f32, 0.0, 0.0]);
+    let y = vec3([0.0f32, 1.0, 0.0]);
+    assert_eq!(x.cross(y), vec3([0.0, 0.0, 1.0]));
+    assert_eq!(x.dot(y), 0.0);
+
+    let v = vec2([3.0f64, 4.0]);
+    assert_eq!(v.length(), 5.0);
+    assert_eq!(v.normalize(), vec2([0.6, 0.8]));
+    assert_eq!(v.distance(vec2([0.0, 0.0])), 5.0);
+}
+
+#[test]
+fn swizzle() {
+    let v = vec4([1, 2, 3, 4]);
+    assert_eq!(v.xyz(), vec3([1, 2, 3]));
+    assert_eq!(v.zyx(), vec3([3, 2, 1]));
+    assert_eq!(v.ww(), vec2([4, 4]));
+    assert_eq!(v.xyz().xxyy(), vec4([1, 1, 2, 2]));
+
+    // Components are numbered from 1, so each result spells the indices of its swizzle.
+    let v2 = vec2([1, 2]);
+    assert_eq!(v2.yx(), vec2([2, 1]));
+    assert_eq!(v2.xyy(), vec3([1, 2, 2]));
+    assert_eq!(v2.yxyx(), vec4([2, 1, 2, 1]));
+
+    let v3 = vec3([1, 2, 3]);
+    assert_eq!(v3.zx(), vec2([3, 1]));
+    assert_eq!(v3.yzx(), vec3([2, 3, 1]));
+    assert_eq!(v3.zzyx(), vec4([3, 3, 2, 1]));
+
+    assert_eq!(v.wy(), vec2([4, 2]));
+    assert_eq!(v.wzy(), vec3([4, 3, 2]));
+    assert_eq!(v.wxzy(), vec4([4, 1, 3, 2]));
+    assert_eq!(v.xyzw(), v);
+    assert_eq!(v.wwww(), vec4([4; 4]));
+}
+
+#[test]
+fn conversions() {
+    let mut v = vec3::from((1u32, 2, 3));
+    v[1] = 5;
+    assert_eq!(<(u32, u32, u32)>::from(v), (1, 5, 3));
+    assert_eq!(<[u32; 3]>::from(v), [1, 5, 3]);
+    assert_eq!(v.into_iter().sum::<u32>(), 9);
+    assert_eq!((&v).into_iter().max(), Some(&5));
+}
+
+#[test]
+fn scalars() {
+    use serde::Serialize;
+    use serde_webgpu::scalar::Scalar;
+    use serde_webgpu::vec::Vector;
+    use serde_webgpu::{serialize_webgpu_with, BoolPolicy, NumericPolicy, WebGPUConfig};
+
+    #[derive(Serialize)]
+    struct Flags {
+        a: u32,
+        b: vec3<bool>,
+        c: vec2<i64>,
+    }
+
+    assert_eq!(<i64 as Scalar>::VECTOR_NAMES[1], "vec3@i64");
+    assert_eq!(Vector::<f32, 3>::default(), vec3([0.0; 3]));
+
+    let flags = Flags {
+        a: 1,
+        b: vec3([true, false, true]),
+        c: vec2([-1, 2]),
+    };
+    let config = WebGPUConfig {
+        numeric: NumericPolicy {
+            narrow_int64: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let bytes = serialize_webgpu_with(&flags, &config).unwrap();
+    assert_eq!(bytes.len(), 48);
+    assert_eq!(bytes[16..24], [1, 0, 0, 0, 0, 0, 0, 0]);
+    assert_eq!(bytes[32..36], [0xFF; 4]);
+
+    let config = WebGPUConfig {
+        bool_policy: BoolPolicy::Pack,
+        ..config
+    };
+    let bytes = serialize_webgpu_with(&flags, &config).unwrap();
+    assert_eq!(bytes[4..8], [0b101, 0, 0, 0]);
+    assert_eq!(bytes[8..16], [0xFF, 0xFF, 0xFF, 0xFF, 2, 0, 0, 0]);
+}